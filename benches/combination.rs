@@ -0,0 +1,111 @@
+// compares the bucketed combination of GroupStructure with the plain pairwise comparison it
+// replaced, over random tables of 10 and 12 variables.
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use common::{input_row, xorshift};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use quine_mccluskey::implicant::VariablesNames;
+use quine_mccluskey::{GroupStructure, Implicant};
+
+// the tables are random but fixed, so every run measures the same functions.
+fn random_minterms(amount_of_variables: usize, seed: u64) -> Vec<Vec<bool>> {
+    let mut next = xorshift(seed);
+
+    (0..1usize << amount_of_variables)
+        .filter(|_| ! next().is_multiple_of(4))
+        .map(|minterm| input_row(minterm, amount_of_variables))
+        .collect()
+}
+
+fn implicants_of(amount_of_variables: usize, minterms: &[Vec<bool>]) -> Vec<Implicant> {
+    let variables: VariablesNames = (0..amount_of_variables)
+        .map(|variable| format!("X{variable}"))
+        .collect();
+    minterms.iter().map(|row| Implicant::from_input(row, &variables, 1)).collect()
+}
+
+fn bucketed(amount_of_variables: usize, implicants: &[Implicant]) -> usize {
+    let mut groups = GroupStructure::new(amount_of_variables, 1);
+    implicants.iter().for_each(|implicant| groups.add_implicant(implicant.clone()));
+    while groups.combination_step().unwrap() {}
+    groups.extract_primes().unwrap().len()
+}
+
+// the previous combination step: every implicant is compared with the whole next group, and
+// repeated implicants are found by scanning the new group.
+fn pairwise(amount_of_variables: usize, implicants: &[Implicant]) -> usize {
+    let mut groups: Vec<Vec<Implicant>> = vec![Vec::new(); amount_of_variables + 1];
+    for implicant in implicants {
+        groups[implicant.amount_of_true_variables()].push(implicant.clone());
+    }
+
+    loop {
+        let mut new_groups: Vec<Vec<Implicant>> = vec![Vec::new(); groups.len()];
+        let mut absorbed: Vec<Vec<bool>> = groups.iter()
+            .map(|group| vec![false; group.len()])
+            .collect();
+        let mut found_some_combinable = false;
+
+        for group_index in 0..groups.len() - 1 {
+            for (implicant_index, implicant) in groups[group_index].iter().enumerate() {
+                for (candidate_index, candidate) in groups[group_index + 1].iter().enumerate() {
+                    if ! implicant.check_if_combines(candidate) { continue }
+
+                    found_some_combinable = true;
+                    absorbed[group_index][implicant_index] = true;
+                    absorbed[group_index + 1][candidate_index] = true;
+                    let new_implicant = Implicant::from_implicants(implicant, candidate).unwrap();
+                    if ! new_groups[group_index].contains(&new_implicant) {
+                        new_groups[group_index].push(new_implicant);
+                    }
+                }
+            }
+        }
+
+        for (group_index, group) in groups.iter().enumerate() {
+            for (implicant_index, implicant) in group.iter().enumerate() {
+                if absorbed[group_index][implicant_index] { continue }
+                let mut prime = implicant.clone();
+                prime.mark_as_prime();
+                new_groups[group_index].push(prime);
+            }
+        }
+
+        groups = new_groups;
+        if ! found_some_combinable { break }
+    }
+
+    groups.iter().map(|group| group.len()).sum()
+}
+
+fn combination(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("combination");
+    group.sample_size(10);
+
+    for amount_of_variables in [10, 12] {
+        let minterms = random_minterms(amount_of_variables, 0x2545_f491_4f6c_dd1d);
+        let implicants = implicants_of(amount_of_variables, &minterms);
+        assert_eq!(
+            bucketed(amount_of_variables, &implicants),
+            pairwise(amount_of_variables, &implicants)
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("bucketed", amount_of_variables),
+            &implicants,
+            |bencher, implicants| bencher.iter(|| bucketed(amount_of_variables, implicants))
+        );
+        group.bench_with_input(
+            BenchmarkId::new("pairwise", amount_of_variables),
+            &implicants,
+            |bencher, implicants| bencher.iter(|| pairwise(amount_of_variables, implicants))
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, combination);
+criterion_main!(benches);
@@ -1,89 +1,595 @@
-use crate::truth_table::TruthTable;
-use crate::implicant::Implicant;
+use crate::truth_table::{OutputValue, TruthTable};
+use crate::implicant::{Implicant, VariablesNames};
 use crate::groups_structure::GroupStructure;
 use crate::coverage_map::CoverageMap;
+use crate::cover_strategy::{CoverStrategy, FirstColumnGreedy};
+use crate::cost_model::CostModel;
+use crate::espresso::espresso;
+use crate::verifier::verify_minimization;
+use crate::error::{QmError, Result};
 use std::io;
 use std::io::{Write};
+use std::sync::Arc;
 
-pub fn algorithm(table: TruthTable, step_by_step: bool) -> String {
-    let mut first_implicants: Vec<Implicant> = Vec::new();
-    for (index, row) in table.input_rows().iter().enumerate() {
-        if table.row_value(index) {
-            first_implicants.push(Implicant::from_input(row, table.variables_names()));
+// shape of the expression produced. a sum of products is built from the ones of the function,
+// while a product of sums is built from its zeros. Best builds both and keeps the cheaper one.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum Form {
+    #[default]
+    SumOfProducts,
+    ProductOfSums,
+    Best
+}
+
+impl Form {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Form::SumOfProducts => "sum of products",
+            Form::ProductOfSums => "product of sums",
+            Form::Best => "best of sum of products and product of sums"
+        }
+    }
+}
+
+// the method used to find the cover. Quine-McCluskey finds every prime before choosing among
+// them, which becomes too slow for big tables, while Espresso grows a cover by heuristics and
+// copes with many more variables. Automatic uses Espresso only for tables with more variables
+// than the threshold given in the options.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum Engine {
+    QuineMcCluskey,
+    Espresso,
+    #[default]
+    Automatic
+}
+
+impl Engine {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Engine::QuineMcCluskey => "qm",
+            Engine::Espresso => "espresso",
+            Engine::Automatic => "auto"
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [Engine::QuineMcCluskey, Engine::Espresso, Engine::Automatic].into_iter()
+            .find(|engine| engine.name() == name)
+    }
+}
+
+// tables with more variables than this are minimized by Espresso, unless an engine is chosen.
+pub const ESPRESSO_THRESHOLD: usize = 16;
+
+// settings that change how a minimization is carried out. the cover strategy chooses the primes
+// left uncovered by the essential ones, guided by the costs the cost model gives them. with
+// all_solutions, every cover of minimum cost is listed too, up to max_solutions of them. these
+// settings only apply to Quine-McCluskey.
+#[derive(Clone, Debug)]
+pub struct Options {
+    pub form: Form,
+    pub engine: Engine,
+    pub espresso_threshold: usize,
+    pub cover: Arc<dyn CoverStrategy>,
+    pub cost_model: CostModel,
+    pub all_solutions: bool,
+    pub max_solutions: Option<usize>
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            form: Form::default(),
+            engine: Engine::default(),
+            espresso_threshold: ESPRESSO_THRESHOLD,
+            cover: Arc::new(FirstColumnGreedy),
+            cost_model: CostModel::default(),
+            all_solutions: false,
+            max_solutions: None
+        }
+    }
+}
+
+// the outcome of a minimization. besides the final cover, it keeps every prime implicant found
+// and tells apart the essential primes from the ones picked afterwards to complete the cover.
+// for tables with several outputs, the cover holds every selected product term once, and each
+// output is built from the ones it needs. terms needed by more than one output are shared.
+// when the form is a product of sums, implicants describe the zeros of each output and are
+// rendered as sum terms. Espresso does not generate every prime, so for its results the primes
+// are only the ones in the cover, and none of them is told apart as essential.
+pub struct Minimization {
+    form: Form,
+    output_names: Vec<String>,
+    primes: Vec<Implicant>,
+    essentials: Vec<Implicant>,
+    chosen: Vec<Implicant>,
+    cover: Vec<Implicant>,
+    outputs_covers: Vec<Vec<Implicant>>,
+    cost: usize,
+    minimum_solutions: Vec<Minimization>
+}
+
+impl Minimization {
+    pub fn form(&self) -> Form { self.form }
+    pub fn output_names(&self) -> &Vec<String> { &self.output_names }
+    pub fn primes(&self) -> &Vec<Implicant> { &self.primes }
+    pub fn essentials(&self) -> &Vec<Implicant> { &self.essentials }
+    pub fn chosen(&self) -> &Vec<Implicant> { &self.chosen }
+    pub fn cover(&self) -> &Vec<Implicant> { &self.cover }
+    pub fn output_cover(&self, output: usize) -> &Vec<Implicant> { &self.outputs_covers[output] }
+
+    // every cover of minimum cost, when requested through Options::all_solutions. each one is
+    // a minimization on its own, sharing the primes and essentials of this one.
+    pub fn minimum_solutions(&self) -> &Vec<Minimization> { &self.minimum_solutions }
+
+    // product terms used by more than one output.
+    pub fn shared_terms(&self) -> Vec<&Implicant> {
+        self.cover.iter()
+            .filter(|term| {
+                self.outputs_covers.iter()
+                    .filter(|output_cover| output_cover.contains(term))
+                    .count() > 1
+            })
+            .collect()
+    }
+
+    // shared terms are only counted once, both here and in the cost.
+    pub fn cost(&self) -> usize { self.cost }
+    pub fn amount_of_terms(&self) -> usize { self.cover.len() }
+    pub fn amount_of_literals(&self) -> usize {
+        self.cover.iter().map(|implicant| implicant.amount_of_literals()).sum()
+    }
+
+    pub fn output_expression(&self, output: usize) -> String {
+        assemble_expression(
+            self.outputs_covers[output].iter().collect::<Vec<&Implicant>>().as_slice(),
+            self.form
+        )
+    }
+
+    // for a single output, this is its formula. otherwise, there is one line for each output,
+    // such as "F = AB + C".
+    pub fn expression(&self) -> String {
+        if self.output_names.len() == 1 { return self.output_expression(0) }
+
+        self.output_names.iter()
+            .enumerate()
+            .map(|(output, name)| format!("{name} = {}", self.output_expression(output)))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+// minimizes the function described by the table into a sum of products, without printing
+// anything.
+pub fn minimize(table: &TruthTable) -> Result<Minimization> {
+    minimize_with(table, &Options::default())
+}
+
+// minimizes the function described by the table without printing anything.
+pub fn minimize_with(table: &TruthTable, options: &Options) -> Result<Minimization> {
+    solve(table, options, &Narrator { verbose: false, step_by_step: false })
+}
+
+// minimizes the function described by the table, printing every stage of the algorithm.
+// with step_by_step, the user is prompted to advance after each stage.
+pub fn algorithm(
+    table: &TruthTable,
+    options: &Options,
+    step_by_step: bool
+) -> Result<Minimization> {
+    solve(table, options, &Narrator { verbose: true, step_by_step })
+}
+
+// decides whether the stages of the algorithm are reported to the user.
+struct Narrator {
+    verbose: bool,
+    step_by_step: bool
+}
+
+impl Narrator {
+    fn say(&self, message: &str) {
+        if self.verbose { println!("{message}") }
+    }
+
+    fn show<F: FnOnce()>(&self, printer: F) {
+        if self.verbose { printer() }
+    }
+
+    fn pause(&self) -> Result<()> {
+        if self.verbose && self.step_by_step {
+            wait_for_user_advance().map_err(|error| QmError::io("standard input", error))?;
         }
+
+        Ok(())
     }
+}
 
-    println!("Read table from provided input:");
-    table.print_table();
+fn solve(table: &TruthTable, options: &Options, narrator: &Narrator) -> Result<Minimization> {
+    match options.form {
+        Form::SumOfProducts => {
+            let minimization = run_engine(table, Form::SumOfProducts, options, narrator)?;
+            check_result(table, minimization, narrator)
+        }
+        Form::ProductOfSums => {
+            narrator.say("A product of sums is built from the zeros of the function, which are \
+            the ones of its complement. The complemented table is used from now on.\n");
+            let minimization =
+                run_engine(&table.complement(), Form::ProductOfSums, options, narrator)?;
+            check_result(table, minimization, narrator)
+        },
+        Form::Best => {
+            let mut sum_of_products_options = options.clone();
+            sum_of_products_options.form = Form::SumOfProducts;
+            let mut product_of_sums_options = options.clone();
+            product_of_sums_options.form = Form::ProductOfSums;
+
+            narrator.say("Both forms will be built. Beginning with the sum of products.\n");
+            let sum_of_products = solve(table, &sum_of_products_options, narrator)?;
+            narrator.say("\nNow building the product of sums.\n");
+            let product_of_sums = solve(table, &product_of_sums_options, narrator)?;
 
-    if step_by_step { wait_for_user_advance() }
+            // on a tie, the sum of products is kept.
+            narrator.say(&format!(
+                "\nThe sum of products costs {} and the product of sums costs {}, in {}.",
+                sum_of_products.cost(), product_of_sums.cost(), options.cost_model.metric.name()
+            ));
+            let pos_is_cheaper = product_of_sums.cost() < sum_of_products.cost();
+            let best = if pos_is_cheaper { product_of_sums } else { sum_of_products };
+            narrator.say(&format!(
+                "The {} is cheaper and was kept:\n{}", best.form().name(), best.expression()
+            ));
+
+            Ok(best)
+        }
+    }
+}
+
+// evaluates the result, and every other cover of minimum cost found, on each row of the table.
+// a disagreement means the minimization went wrong.
+fn check_result(
+    table: &TruthTable,
+    minimization: Minimization,
+    narrator: &Narrator
+) -> Result<Minimization> {
+    let results = std::iter::once(&minimization).chain(&minimization.minimum_solutions);
+    for result in results {
+        let counterexamples = verify_minimization(table, result);
+        if let Some(counterexample) = counterexamples.first() {
+            return Err(QmError::Internal(format!(
+                "the result {} disagrees with the table. At {}. Disagreements found: {}",
+                result.expression(), counterexample.describe(table), counterexamples.len()
+            )));
+        }
+    }
+
+    narrator.say("\nThe result was evaluated on every row of the table, and agrees with all of \
+    them.");
+    Ok(minimization)
+}
 
-    println!("\nFunction is defined by the unoptimized expression:");
-    let function_defining_expression = assemble_expression(
-        &first_implicants.iter()
-            .map(|ref_to_implicant| ref_to_implicant)
-            .collect::<Vec<&Implicant>>()
-            .as_slice()
+fn run_engine(
+    table: &TruthTable,
+    form: Form,
+    options: &Options,
+    narrator: &Narrator
+) -> Result<Minimization> {
+    let use_espresso = match options.engine {
+        Engine::QuineMcCluskey => false,
+        Engine::Espresso => true,
+        Engine::Automatic => table.amount_of_variables() > options.espresso_threshold
+    };
+
+    if ! use_espresso { return run(table, form, options, narrator) }
+
+    if options.engine == Engine::Automatic {
+        narrator.say(&format!(
+            "The table has more than {} variables, so it will be minimized by the Espresso \
+            heuristic instead of Quine-McCluskey.\n",
+            options.espresso_threshold
+        ));
+    }
+    run_espresso(table, form, options, narrator)
+}
+
+fn run_espresso(
+    table: &TruthTable,
+    form: Form,
+    options: &Options,
+    narrator: &Narrator
+) -> Result<Minimization> {
+    let output_names = table.output_names();
+    let is_sum_term = form == Form::ProductOfSums;
+    let cost_of = |implicant: &Implicant| options.cost_model.implicant_cost(implicant, is_sum_term);
+
+    narrator.say("Read table from provided input:");
+    narrator.show(|| table.print_table());
+    narrator.pause()?;
+
+    narrator.say("\nBeginning heuristic optimization by the Espresso algorithm.");
+    narrator.say("Starting from the ones of the function, the cover is expanded into primes, its \
+    redundant terms are dropped, and then it is reduced so the next expansion may find a cheaper \
+    cover. This goes on while the cost keeps lowering.");
+    let espresso = espresso(table, &cost_of)?;
+
+    for step in &espresso.steps {
+        narrator.say(&format!("\n{}:", step.name));
+        for (output, name) in output_names.iter().enumerate() {
+            let terms: Vec<&Implicant> = step.cover.iter()
+                .filter(|implicant| implicant.implies_output(output))
+                .collect();
+            narrator.say(&format!("{name} = {}", assemble_expression(&terms, form)));
+        }
+        narrator.pause()?;
+    }
+
+    let minimization = Minimization {
+        form,
+        output_names: output_names.to_vec(),
+        primes: espresso.cover.clone(),
+        essentials: Vec::new(),
+        chosen: espresso.cover.clone(),
+        cost: espresso.cover.iter().map(cost_of).sum(),
+        cover: espresso.cover,
+        outputs_covers: espresso.outputs_covers,
+        minimum_solutions: Vec::new()
+    };
+
+    narrator.say(
+        "\nOptimization process is finished. An equivalent formula for the provided function is:"
     );
-    println!("{}", function_defining_expression);
+    narrator.say(&minimization.expression());
+    let shared_terms = minimization.shared_terms();
+    if ! shared_terms.is_empty() {
+        narrator.say(
+            &format!("Shared terms: {}", assemble_expression(shared_terms.as_slice(), form))
+        );
+    }
+    narrator.say(&describe_cost(&minimization, &options.cost_model));
+    if options.all_solutions {
+        narrator.say("\nEspresso does not search for every cover of minimum cost, so none are \
+        listed.");
+    }
 
-    if step_by_step { wait_for_user_advance() }
+    Ok(minimization)
+}
 
-    println!("\nBeggining iterative optimization by Quine-McCluskey algorithm.");
-    println!("Primes found will be marked with an *.");
-    let amount_of_variables = table.amount_of_variables();
-    let mut groups = agroup(first_implicants.clone(), amount_of_variables);
-    groups.print_group();
+fn run(
+    table: &TruthTable,
+    form: Form,
+    options: &Options,
+    narrator: &Narrator
+) -> Result<Minimization> {
+    // don't-care minterms take part in the combinations, so they can help building bigger
+    // primes, but they are left out of the coverage map since they do not need to be covered.
+    // every minterm is tagged with the outputs it belongs to.
+    let mut first_implicants: Vec<Implicant> = Vec::new();
+    let mut dont_cares: Vec<Implicant> = Vec::new();
+    let mut combinable_implicants: Vec<Implicant> = Vec::new();
+    let variables: VariablesNames = table.variables_names().into();
+    for (index, row) in table.input_rows().iter().enumerate() {
+        let true_outputs = table.outputs_mask(index, OutputValue::True);
+        let dont_care_outputs = table.outputs_mask(index, OutputValue::DontCare);
+
+        if true_outputs != 0 {
+            first_implicants.push(Implicant::from_input(row, &variables, true_outputs));
+        }
+        if dont_care_outputs != 0 {
+            dont_cares.push(Implicant::from_input(row, &variables, dont_care_outputs));
+        }
+        if true_outputs | dont_care_outputs != 0 {
+            combinable_implicants.push(Implicant::from_input(
+                row, &variables, true_outputs | dont_care_outputs
+            ));
+        }
+    }
 
-    if step_by_step { wait_for_user_advance() }
+    let output_names = table.output_names();
+    let implicants_of = |implicants: &[Implicant], output: usize| -> String {
+        assemble_expression(
+            implicants.iter()
+                .filter(|implicant| implicant.implies_output(output))
+                .collect::<Vec<&Implicant>>()
+                .as_slice(),
+            form
+        )
+    };
+
+    narrator.say("Read table from provided input:");
+    narrator.show(|| table.print_table());
+    narrator.pause()?;
+
+    narrator.say("\nFunction is defined by the unoptimized expression:");
+    for (output, name) in output_names.iter().enumerate() {
+        narrator.say(&format!("{name} = {}", implicants_of(&first_implicants, output)));
+    }
+    if ! dont_cares.is_empty() {
+        narrator.say("\nThese minterms are don't-cares, and may be used to build bigger primes:");
+        for (output, name) in output_names.iter().enumerate() {
+            if dont_cares.iter().any(|implicant| implicant.implies_output(output)) {
+                narrator.say(&format!("{name}: {}", implicants_of(&dont_cares, output)));
+            }
+        }
+    }
+    narrator.pause()?;
+
+    narrator.say("\nBeggining iterative optimization by Quine-McCluskey algorithm.");
+    narrator.say("Primes found will be marked with an *.");
+    if output_names.len() > 1 {
+        narrator.say("The outputs implied by each implicant are shown between brackets.");
+    }
+    let amount_of_variables = table.amount_of_variables();
+    let mut groups = agroup(combinable_implicants, amount_of_variables, output_names.len());
+    narrator.show(|| groups.print_group());
+    narrator.pause()?;
 
     let mut iteration: usize = 1;
     loop {
-        println!("\nIteration {iteration}");
-        let should_continue = groups.combination_step();
-        groups.print_group();
+        narrator.say(&format!("\nIteration {iteration}"));
+        let should_continue = groups.combination_step()?;
+        narrator.show(|| groups.print_group());
         iteration += 1;
-        if step_by_step { wait_for_user_advance() }
+        narrator.pause()?;
         if ! should_continue { break }
     }
 
-    println!("\nAll prime implicants were found. We will now search for the essential ones.");
-    println!("This is the coverage map for all primes:");
-    let primes = groups.extract_primes();
-    let mut coverage_map = CoverageMap::new(&primes, &first_implicants);
-    coverage_map.print();
+    narrator.say("\nAll prime implicants were found. We will now search for the essential ones.");
+    narrator.say("This is the coverage map for all primes:");
+    let primes = groups.extract_primes()?;
+    let mut coverage_map = CoverageMap::new(&primes, &first_implicants, output_names)?;
+    coverage_map.set_primes_costs(
+        primes.iter()
+            .map(|prime| options.cost_model.implicant_cost(prime, form == Form::ProductOfSums))
+            .collect()
+    );
+    narrator.show(|| coverage_map.print());
+    narrator.pause()?;
+
+    let essentials_found = coverage_map.find_essentials()?;
+    narrator.say(&format!("\nEssential primes were marked in green ({essentials_found} found):"));
+    narrator.show(|| coverage_map.print());
+    narrator.pause()?;
 
-    if step_by_step { wait_for_user_advance() }
+    let essentials_indexes = coverage_map.get_selected_implicants().clone();
 
-    let essentials_found = coverage_map.find_essentials();
-    println!("\nEssential primes were marked in green ({essentials_found} found):");
-    coverage_map.print();
+    // reductions may drop primes that take part in some of the minimum covers, so these are
+    // searched for beforehand.
+    let minimum_covers = if options.all_solutions {
+        coverage_map.minimum_covers(options.max_solutions)?
+    } else { Vec::new() };
 
-    if step_by_step { wait_for_user_advance() }
+    narrator.say("\nThe map is now reduced by dominance until only its cyclic core is left.");
+    narrator.say("Removed rows and columns are printed in red, with their marks crossed out.");
+    let mut round: usize = 1;
+    loop {
+        let reduction = coverage_map.reduction_round()?;
+        if reduction.is_empty() {
+            if round == 1 { narrator.say("No reduction applies, the map is already its core.") }
+            break
+        }
 
-    println!("\nArbitrary selection of implicants to cover the remaining minterms:");
-    coverage_map.choose_remaining_primes();
-    coverage_map.print();
+        let names = |indexes: &[usize], name_of: &dyn Fn(usize) -> String| -> String {
+            if indexes.is_empty() { return String::from("none") }
+            indexes.iter().map(|index| name_of(*index)).collect::<Vec<String>>().join(", ")
+        };
+        let minterm_name = |index: usize| coverage_map.minterm_name(index).to_string();
+        let prime_name = |index: usize| coverage_map.prime_name(index).to_string();
+        narrator.say(&format!("\nReduction round {round}:"));
+        narrator.say(&format!(
+            "Dominating minterms removed: {}",
+            names(&reduction.dominating_minterms, &minterm_name)
+        ));
+        narrator.say(&format!(
+            "Dominated primes removed: {}", names(&reduction.dominated_primes, &prime_name)
+        ));
+        narrator.say(&format!(
+            "New essential primes: {}", names(&reduction.essentials, &prime_name)
+        ));
+        narrator.show(|| coverage_map.print());
+        narrator.pause()?;
+        round += 1;
+    }
 
-    if step_by_step { wait_for_user_advance() }
+    let cover = options.cover.as_ref();
+    narrator.say(&format!(
+        "\nSelection of implicants to cover the remaining minterms, by the {} strategy:",
+        cover.name()
+    ));
+    coverage_map.choose_remaining_primes_with(cover)?;
+    narrator.show(|| coverage_map.print_cover_explanation());
+    narrator.show(|| coverage_map.print());
+    narrator.pause()?;
 
-    println!(
+    let mut minimization = assemble_minimization(
+        form, output_names, &primes, &essentials_indexes, coverage_map.get_selected_implicants(),
+        &coverage_map
+    );
+    minimization.minimum_solutions = minimum_covers.iter()
+        .map(|cover| assemble_minimization(
+            form, output_names, &primes, &essentials_indexes, cover, &coverage_map
+        ))
+        .collect();
+
+    narrator.say(
         "\nOptimization process is finished. An equivalent formula for the provided function is:"
     );
-    let selected_implicants_indexes = coverage_map.get_selected_implicants();
-    let selected_implicants: Vec<&Implicant> = primes.iter()
-        .enumerate()
-        .filter(|(index, _)| selected_implicants_indexes.contains(index))
-        .map(|(_, prime)| prime)
+    narrator.say(&minimization.expression());
+    let shared_terms = minimization.shared_terms();
+    if ! shared_terms.is_empty() {
+        narrator.say(
+            &format!("Shared terms: {}", assemble_expression(shared_terms.as_slice(), form))
+        );
+    }
+    narrator.say(&describe_cost(&minimization, &options.cost_model));
+
+    if options.all_solutions {
+        narrator.say(&format!(
+            "\nThese are the covers of minimum cost ({} listed):",
+            minimization.minimum_solutions.len()
+        ));
+        for (index, solution) in minimization.minimum_solutions.iter().enumerate() {
+            narrator.say(&format!("\nSolution {}:\n{}", index + 1, solution.expression()));
+            narrator.say(&describe_cost(solution, &options.cost_model));
+        }
+    }
+
+    Ok(minimization)
+}
+
+// builds the result for one cover of the coverage map, given as indexes of its primes.
+fn assemble_minimization(
+    form: Form,
+    output_names: &[String],
+    primes: &[Implicant],
+    essentials_indexes: &[usize],
+    cover_indexes: &[usize],
+    coverage_map: &CoverageMap
+) -> Minimization {
+    let outputs_indexes: Vec<Vec<usize>> = (0..output_names.len())
+        .map(|output| coverage_map.output_implicants_of(cover_indexes, output))
+        .collect();
+    let outputs_covers = outputs_indexes.iter()
+        .map(|output_indexes| pick_primes(primes, |index| output_indexes.contains(&index)))
+        .collect();
+
+    // primes that every output left out are not part of the result, nor of its cost.
+    let used_indexes: Vec<usize> = cover_indexes.iter()
+        .copied()
+        .filter(|index| outputs_indexes.iter().any(|output_indexes| output_indexes.contains(index)))
         .collect();
-    let final_formula = assemble_expression(selected_implicants.as_slice());
-    println!("{final_formula}");
 
-    final_formula
+    let costs = coverage_map.get_primes_costs();
+    Minimization {
+        form,
+        output_names: output_names.to_vec(),
+        primes: primes.to_vec(),
+        essentials: pick_primes(primes, |index| essentials_indexes.contains(&index)),
+        chosen: pick_primes(primes, |index| {
+            used_indexes.contains(&index) && ! essentials_indexes.contains(&index)
+        }),
+        cover: pick_primes(primes, |index| used_indexes.contains(&index)),
+        outputs_covers,
+        cost: used_indexes.iter().map(|index| costs[*index]).sum(),
+        minimum_solutions: Vec::new()
+    }
+}
+
+fn describe_cost(minimization: &Minimization, cost_model: &CostModel) -> String {
+    format!(
+        "({} terms, {} literals, costing {} in {})",
+        minimization.amount_of_terms(),
+        minimization.amount_of_literals(),
+        minimization.cost(),
+        cost_model.metric.name()
+    )
 }
 
-fn agroup(implicants: Vec<Implicant>, amount_of_variables: usize) -> GroupStructure {
-    let mut groups = GroupStructure::new(amount_of_variables);
+fn agroup(
+    implicants: Vec<Implicant>,
+    amount_of_variables: usize,
+    amount_of_outputs: usize
+) -> GroupStructure {
+    let mut groups = GroupStructure::new(amount_of_variables, amount_of_outputs);
     for implicant in implicants {
         groups.add_implicant(implicant);
     }
@@ -91,19 +597,41 @@ fn agroup(implicants: Vec<Implicant>, amount_of_variables: usize) -> GroupStruct
     groups
 }
 
-fn assemble_expression(implicants: &[&Implicant]) -> String {
+fn pick_primes<F: Fn(usize) -> bool>(primes: &[Implicant], filter: F) -> Vec<Implicant> {
+    primes.iter()
+        .enumerate()
+        .filter(|(index, _)| filter(*index))
+        .map(|(_, prime)| prime.clone())
+        .collect()
+}
+
+fn assemble_expression(implicants: &[&Implicant], form: Form) -> String {
+    if form == Form::ProductOfSums {
+        // a product with no sum terms at all is constant true.
+        if implicants.is_empty() { return String::from("1") }
+
+        return implicants.iter()
+            .map(|implicant| implicant.get_clause_representation())
+            .collect::<Vec<String>>()
+            .join("")
+    }
+
+    // a function with no implicants at all is constant false.
+    if implicants.is_empty() { return String::from("0") }
+
     implicants.iter()
         .map(|implicant| implicant.get_string_representation())
         .collect::<Vec<String>>()
         .join(" + ")
 }
 
-fn wait_for_user_advance() {
+fn wait_for_user_advance() -> io::Result<()> {
     let stdin = io::stdin();
     let mut stdout = io::stdout();
 
-    write!(stdout, "\nPress any key to continue.").unwrap();
-    stdout.flush().unwrap();
+    write!(stdout, "\nPress any key to continue.")?;
+    stdout.flush()?;
 
-    let _ = stdin.read_line(&mut String::new()).unwrap();
+    stdin.read_line(&mut String::new())?;
+    Ok(())
 }
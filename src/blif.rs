@@ -0,0 +1,195 @@
+use std::fs;
+use crate::error::{QmError, Result};
+use crate::table_parser::{all_input_rows, ParsedTable};
+use crate::truth_table::OutputValue;
+use crate::algorithm::{Form, Minimization};
+
+// the cover of a single output, as given by a .names block: its inputs, and rows such as 1-0 1.
+struct NamesBlock {
+    line: usize,
+    inputs: Vec<String>,
+    output: String,
+    rows: Vec<(String, char)>
+}
+
+// reads a combinational model in the Berkeley BLIF format. only single-level logic is accepted:
+// every .names block must compute an output of the model straight from its inputs.
+pub fn read_blif(path: &str) -> Result<ParsedTable> {
+    let content = fs::read_to_string(path).map_err(|error| QmError::io(path, error))?;
+
+    let mut variables: Option<Vec<String>> = None;
+    let mut output_names: Option<Vec<String>> = None;
+    let mut blocks: Vec<NamesBlock> = Vec::new();
+
+    for (line, words) in logical_lines(&content) {
+        let syntax_error = |message: String| QmError::Syntax { line, message };
+        let directive = words[0].as_str();
+        let arguments = words[1..].to_vec();
+
+        match directive {
+            ".model" => {},
+            ".inputs" => variables.get_or_insert_with(Vec::new).extend(arguments),
+            ".outputs" => output_names.get_or_insert_with(Vec::new).extend(arguments),
+            ".names" => {
+                let Some((output, inputs)) = arguments.split_last() else {
+                    return Err(syntax_error(String::from(".names needs at least an output")))
+                };
+                blocks.push(NamesBlock {
+                    line, inputs: inputs.to_vec(), output: output.clone(), rows: Vec::new()
+                });
+            }
+            ".end" => break,
+            _ if directive.starts_with('.') => {
+                return Err(syntax_error(format!("unsupported directive {directive}")))
+            }
+            _ => {
+                let Some(block) = blocks.last_mut() else {
+                    return Err(syntax_error(String::from("cover rows must follow a .names")))
+                };
+
+                // blocks without inputs have rows made of the output value alone.
+                let (cube, value) = match (block.inputs.is_empty(), words.as_slice()) {
+                    (true, [value]) => (String::new(), value.as_str()),
+                    (false, [cube, value]) => (cube.clone(), value.as_str()),
+                    _ => return Err(syntax_error(format!(
+                        "expected a cube of {} inputs followed by an output value",
+                        block.inputs.len()
+                    )))
+                };
+                let value = match value {
+                    "0" => '0',
+                    "1" => '1',
+                    _ => return Err(syntax_error(format!("invalid output value '{value}'")))
+                };
+                if cube.chars().count() != block.inputs.len()
+                    || cube.chars().any(|symbol| ! "01-".contains(symbol)) {
+                    return Err(syntax_error(format!(
+                        "'{cube}' is not a cube of the {} inputs of .names {}",
+                        block.inputs.len(), block.output
+                    )));
+                }
+                if block.rows.iter().any(|(_, other)| *other != value) {
+                    return Err(syntax_error(format!(
+                        "the rows of .names {} must all give the same output value",
+                        block.output
+                    )));
+                }
+
+                block.rows.push((cube, value));
+            }
+        }
+    }
+
+    let variables = variables
+        .ok_or_else(|| QmError::InvalidHeader(String::from("the .inputs directive is missing")))?;
+    let output_names = output_names
+        .ok_or_else(|| QmError::InvalidHeader(String::from("the .outputs directive is missing")))?;
+
+    let inputs = all_input_rows(variables.len())?;
+    let mut outputs = vec![vec![OutputValue::False; output_names.len()]; inputs.len()];
+    let mut driven = vec![false; output_names.len()];
+
+    for block in &blocks {
+        let syntax_error = |message: String| QmError::Syntax { line: block.line, message };
+        let output = output_names.iter()
+            .position(|name| *name == block.output)
+            .ok_or_else(|| syntax_error(format!(
+                "{} is not an output of the model. Only single-level logic is supported",
+                block.output
+            )))?;
+        if driven[output] {
+            return Err(syntax_error(format!("{} is computed more than once", block.output)));
+        }
+        driven[output] = true;
+
+        let positions = block.inputs.iter()
+            .map(|input| {
+                variables.iter().position(|name| name == input).ok_or_else(|| syntax_error(
+                    format!("{input} is not an input of the model. Only single-level logic is \
+                    supported")
+                ))
+            })
+            .collect::<Result<Vec<usize>>>()?;
+
+        // rows giving 0 describe where the output is false, and it is true everywhere else.
+        let describes_zeros = block.rows.first().is_some_and(|(_, value)| *value == '0');
+        for (row, values) in inputs.iter().enumerate() {
+            let matches_some_cube = block.rows.iter().any(|(cube, _)| {
+                cube.chars().zip(positions.iter()).all(|(symbol, position)| match symbol {
+                    '0' => ! values[*position],
+                    '1' => values[*position],
+                    _ => true
+                })
+            });
+
+            let is_true = matches_some_cube != describes_zeros;
+            outputs[row][output] = if is_true { OutputValue::True } else { OutputValue::False };
+        }
+    }
+
+    if let Some(output) = driven.iter().position(|driven| ! driven) {
+        return Err(QmError::InvalidHeader(format!(
+            "output {} is not computed by any .names", output_names[output]
+        )));
+    }
+
+    Ok((variables, output_names, inputs, outputs))
+}
+
+// writes the minimized expression as a BLIF model with a .names block for each output. each
+// term becomes a cube of the block. a product of sums is written with cubes giving 0, which
+// describe where the output is false.
+pub fn write_blif(minimization: &Minimization, variables: &[String], model: &str) -> String {
+    let output_names = minimization.output_names();
+    let is_product_of_sums = minimization.form() == Form::ProductOfSums;
+    let mut blif = format!(
+        ".model {model}\n.inputs {}\n.outputs {}\n",
+        variables.join(" "), output_names.join(" ")
+    );
+
+    for (output, name) in output_names.iter().enumerate() {
+        let cover = minimization.output_cover(output);
+
+        // a .names block with no cubes is constant false, so an empty product of sums, which is
+        // constant true, is written as a block with no inputs that gives 1.
+        if cover.is_empty() {
+            blif.push_str(&format!(".names {name}\n"));
+            if is_product_of_sums { blif.push_str("1\n") }
+            continue
+        }
+
+        blif.push_str(&format!(".names {} {name}\n", variables.join(" ")));
+        let value = if is_product_of_sums { '0' } else { '1' };
+        for term in cover {
+            blif.push_str(&format!("{} {value}\n", term.get_binary_representation()));
+        }
+    }
+
+    blif.push_str(".end\n");
+    blif
+}
+
+// the lines of a BLIF file split into words, with their number. comments are dropped, and lines
+// ending with a backslash are joined with the next one.
+fn logical_lines(content: &str) -> Vec<(usize, Vec<String>)> {
+    let mut lines: Vec<(usize, Vec<String>)> = Vec::new();
+    let mut continues_previous = false;
+
+    for (index, raw_line) in content.lines().enumerate() {
+        let line = raw_line.split('#').next().unwrap_or("");
+        let (line, continues_next) = match line.trim_end().strip_suffix('\\') {
+            Some(line) => (line, true),
+            None => (line, false)
+        };
+        let words: Vec<String> = line.split_whitespace().map(String::from).collect();
+
+        match lines.last_mut() {
+            Some((_, previous_words)) if continues_previous => previous_words.extend(words),
+            _ => lines.push((index + 1, words))
+        }
+        continues_previous = continues_next;
+    }
+
+    lines.retain(|(_, words)| ! words.is_empty());
+    lines
+}
@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+use crate::implicant::Implicant;
+
+// what the cost of a cover measures.
+// - Literals: the literals of every term, which is the default;
+// - Terms: the amount of terms, each one costing 1;
+// - GateInputs: the inputs of every gate of a two level circuit. terms with a single literal
+//   need no gate of their own, so they cost that literal alone, and every other term is one
+//   more input for the gate that joins them.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum CostMetric {
+    #[default]
+    Literals,
+    Terms,
+    GateInputs
+}
+
+impl CostMetric {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CostMetric::Literals => "literals",
+            CostMetric::Terms => "terms",
+            CostMetric::GateInputs => "gate-inputs"
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [CostMetric::Literals, CostMetric::Terms, CostMetric::GateInputs].into_iter()
+            .find(|metric| metric.name() == name)
+    }
+}
+
+// the weights accepted from the command line. keeping them this small lets the costs of whole
+// covers be added up without overflowing. the cost of a single term saturates instead of
+// overflowing whatever the weights are.
+pub const MAX_WEIGHT: usize = 1000;
+
+// prices the terms of an expression. each literal costs the weight of its variable (1 unless
+// given) times the weight of its polarity, so, for instance, complemented literals can be made
+// more expensive when only the true rails of the inputs are available.
+#[derive(Clone, Debug)]
+pub struct CostModel {
+    pub metric: CostMetric,
+    pub variables_weights: HashMap<String, usize>,
+    pub true_weight: usize,
+    pub complemented_weight: usize
+}
+
+impl Default for CostModel {
+    fn default() -> Self {
+        CostModel {
+            metric: CostMetric::default(),
+            variables_weights: HashMap::new(),
+            true_weight: 1,
+            complemented_weight: 1
+        }
+    }
+}
+
+impl CostModel {
+    pub fn variable_weight(&self, variable: &str) -> usize {
+        self.variables_weights.get(variable).copied().unwrap_or(1)
+    }
+
+    // cost of a term of the expression. when the implicant describes the zeros of the function,
+    // it is rendered as a sum term, and the polarity of its literals is inverted.
+    pub fn implicant_cost(&self, implicant: &Implicant, is_sum_term: bool) -> usize {
+        let literals = implicant.literals();
+        let literals_cost: usize = literals.iter()
+            .map(|(variable, is_true)| {
+                let polarity_weight = if *is_true != is_sum_term { self.true_weight }
+                    else { self.complemented_weight };
+                self.variable_weight(variable).saturating_mul(polarity_weight)
+            })
+            .fold(0, usize::saturating_add);
+
+        match self.metric {
+            CostMetric::Literals => literals_cost,
+            CostMetric::Terms => 1,
+            CostMetric::GateInputs => match literals.len() {
+                // the constant term still takes an input of the joining gate.
+                0 => 1,
+                1 => literals_cost,
+                _ => literals_cost.saturating_add(1)
+            }
+        }
+    }
+}
@@ -0,0 +1,502 @@
+use std::fmt::Debug;
+use std::sync::Arc;
+use crate::error::{QmError, Result};
+
+// chooses primes to cover the minterms that the essential primes left uncovered.
+// the incidence matrix has one row for each prime and one column for each uncovered minterm,
+// and tells whether the prime covers the minterm. costs holds the cost of each prime.
+pub trait CoverStrategy: Debug + Send + Sync {
+    fn name(&self) -> &'static str;
+    fn select(&self, incidence: &[Vec<bool>], costs: &[usize]) -> Result<CoverSelection>;
+}
+
+// the primes chosen by a strategy, as row indexes of the incidence matrix. the explanation
+// holds lines describing how they were found, in which the i-th prime is called Pi.
+#[derive(Clone, Debug, Default)]
+pub struct CoverSelection {
+    pub primes: Vec<usize>,
+    pub explanation: Vec<String>
+}
+
+// the strategies shipped with the crate, in the order they are listed to the user.
+pub fn available_strategies() -> Vec<Arc<dyn CoverStrategy>> {
+    vec![
+        Arc::new(FirstColumnGreedy),
+        Arc::new(MaxCoverageGreedy),
+        Arc::new(BranchAndBound),
+        Arc::new(Petrick)
+    ]
+}
+
+pub fn strategy_by_name(name: &str) -> Option<Arc<dyn CoverStrategy>> {
+    available_strategies().into_iter().find(|strategy| strategy.name() == name)
+}
+
+// walks the minterms in column order. for each one still uncovered, selects the prime that
+// covers more yet uncovered minterms. ties are broken by the cheapest prime, and then by the
+// first one.
+#[derive(Debug)]
+pub struct FirstColumnGreedy;
+
+impl CoverStrategy for FirstColumnGreedy {
+    fn name(&self) -> &'static str { "greedy" }
+
+    fn select(&self, incidence: &[Vec<bool>], costs: &[usize]) -> Result<CoverSelection> {
+        let amount_of_minterms = amount_of_columns(incidence);
+        let mut covered = vec![false; amount_of_minterms];
+        let mut primes = Vec::new();
+
+        for minterm in 0..amount_of_minterms {
+            if covered[minterm] { continue }
+
+            let candidate = (0..incidence.len())
+                .filter(|prime| incidence[*prime][minterm])
+                .map(|prime| (prime, covers_how_many_uncovered(&incidence[prime], &covered)))
+                // max_by_key keeps the last maximum, so the candidates are reversed.
+                .rev()
+                .max_by_key(|(prime, new_covertures)| {
+                    (*new_covertures, usize::MAX - costs[*prime])
+                })
+                .map(|(prime, _)| prime)
+                .ok_or_else(|| uncoverable(minterm))?;
+
+            select(candidate, &incidence[candidate], &mut covered, &mut primes);
+        }
+
+        Ok(CoverSelection { primes, explanation: Vec::new() })
+    }
+}
+
+// repeatedly selects the prime that covers more yet uncovered minterms, looking at all of them
+// at once. ties are broken by the cheapest prime.
+#[derive(Debug)]
+pub struct MaxCoverageGreedy;
+
+impl CoverStrategy for MaxCoverageGreedy {
+    fn name(&self) -> &'static str { "max-coverage" }
+
+    fn select(&self, incidence: &[Vec<bool>], costs: &[usize]) -> Result<CoverSelection> {
+        let primes = max_coverage_cover(incidence, costs)?;
+        Ok(CoverSelection { primes, explanation: Vec::new() })
+    }
+}
+
+// exact search for the cheapest cover. the uncovered minterm with less candidates is covered
+// first, trying each of its primes, and branches that can not beat the best cover found so
+// far are pruned. the max-coverage greedy cover is the first bound.
+#[derive(Debug)]
+pub struct BranchAndBound;
+
+impl CoverStrategy for BranchAndBound {
+    fn name(&self) -> &'static str { "branch-and-bound" }
+
+    fn select(&self, incidence: &[Vec<bool>], costs: &[usize]) -> Result<CoverSelection> {
+        let initial = max_coverage_cover(incidence, costs)?;
+        let mut search = Search {
+            incidence: Incidence::new(incidence),
+            costs,
+            best_cost: (cost_of(&initial, costs), initial.len()),
+            best: initial,
+            explored: 0
+        };
+
+        let mut covered = vec![false; amount_of_columns(incidence)];
+        search.branch(&mut Vec::new(), &mut covered, 0);
+
+        let explanation = vec![format!(
+            "Explored {} branches. The cheapest cover costs {}.",
+            search.explored, search.best_cost.0
+        )];
+        Ok(CoverSelection { primes: search.best, explanation })
+    }
+}
+
+// covers are compared by cost, and then by their amount of primes.
+struct Search<'a> {
+    incidence: Incidence,
+    costs: &'a [usize],
+    best: Vec<usize>,
+    best_cost: (usize, usize),
+    explored: usize
+}
+
+impl Search<'_> {
+    fn branch(&mut self, chosen: &mut Vec<usize>, covered: &mut Vec<bool>, cost: usize) {
+        self.explored += 1;
+
+        if cost + lower_bound(&self.incidence, self.costs, covered, |_| true) > self.best_cost.0 {
+            return
+        }
+
+        // the minterm with less candidates is the one that branches less.
+        let next_minterm = (0..covered.len())
+            .filter(|minterm| ! covered[*minterm])
+            .min_by_key(|minterm| self.incidence.primes_of[*minterm].len());
+
+        let minterm = match next_minterm {
+            Some(minterm) => minterm,
+            // branches are only followed while cheaper than the best cover, so this is a new
+            // best one.
+            None => {
+                self.best = chosen.clone();
+                self.best_cost = (cost, chosen.len());
+                return
+            }
+        };
+
+        let mut candidates = self.incidence.primes_of[minterm].clone();
+        candidates.sort_by_key(|prime| self.costs[*prime]);
+
+        for prime in candidates {
+            let new_cost = cost + self.costs[prime];
+            if (new_cost, chosen.len() + 1) >= self.best_cost { continue }
+
+            let newly_covered: Vec<usize> = self.incidence.minterms_of[prime].iter()
+                .copied()
+                .filter(|column| ! covered[*column])
+                .collect();
+            newly_covered.iter().for_each(|column| covered[*column] = true);
+            chosen.push(prime);
+
+            self.branch(chosen, covered, new_cost);
+
+            chosen.pop();
+            newly_covered.iter().for_each(|column| covered[*column] = false);
+        }
+    }
+}
+
+// every cover costing minimum_cost, which must be the cost of the cheapest cover, up to limit
+// covers. the search branches like BranchAndBound, pruning the branches that would cost more.
+// once a candidate prime of a minterm has been tried, it is left out of the branches of the
+// following candidates, so no cover is found twice. the search stops as soon as limit covers
+// are found.
+pub fn minimum_covers(
+    incidence: &[Vec<bool>],
+    costs: &[usize],
+    minimum_cost: usize,
+    limit: Option<usize>
+) -> Result<Vec<Vec<usize>>> {
+    let mut enumeration = Enumeration {
+        incidence: Incidence::new(incidence),
+        costs,
+        best_cost: minimum_cost,
+        limit,
+        covers: Vec::new()
+    };
+
+    let mut covered = vec![false; amount_of_columns(incidence)];
+    let mut excluded = vec![false; incidence.len()];
+    enumeration.branch(&mut Vec::new(), &mut covered, &mut excluded, 0);
+
+    let mut covers = enumeration.covers;
+    covers.iter_mut().for_each(|cover| cover.sort());
+    covers.sort();
+
+    Ok(covers)
+}
+
+struct Enumeration<'a> {
+    incidence: Incidence,
+    costs: &'a [usize],
+    best_cost: usize,
+    limit: Option<usize>,
+    covers: Vec<Vec<usize>>
+}
+
+impl Enumeration<'_> {
+    fn is_done(&self) -> bool {
+        self.limit.is_some_and(|limit| self.covers.len() >= limit)
+    }
+
+    fn branch(
+        &mut self,
+        chosen: &mut Vec<usize>,
+        covered: &mut Vec<bool>,
+        excluded: &mut Vec<bool>,
+        cost: usize
+    ) {
+        if self.is_done() { return }
+
+        let remaining = lower_bound(&self.incidence, self.costs, covered, |prime| ! excluded[prime]);
+        if cost + remaining > self.best_cost { return }
+
+        let candidates_of = |minterm: usize| -> Vec<usize> {
+            self.incidence.primes_of[minterm].iter()
+                .copied()
+                .filter(|prime| ! excluded[*prime])
+                .collect()
+        };
+
+        let next_minterm = (0..covered.len())
+            .filter(|minterm| ! covered[*minterm])
+            .min_by_key(|minterm| {
+                self.incidence.primes_of[*minterm].iter().filter(|prime| ! excluded[**prime]).count()
+            });
+
+        let minterm = match next_minterm {
+            Some(minterm) => minterm,
+            None => {
+                self.covers.push(chosen.clone());
+                return
+            }
+        };
+
+        let candidates = candidates_of(minterm);
+        let mut newly_excluded = Vec::new();
+        for prime in candidates {
+            if self.is_done() { break }
+
+            let new_cost = cost + self.costs[prime];
+            if new_cost <= self.best_cost {
+                let newly_covered: Vec<usize> = self.incidence.minterms_of[prime].iter()
+                    .copied()
+                    .filter(|column| ! covered[*column])
+                    .collect();
+                newly_covered.iter().for_each(|column| covered[*column] = true);
+                chosen.push(prime);
+
+                self.branch(chosen, covered, excluded, new_cost);
+
+                chosen.pop();
+                newly_covered.iter().for_each(|column| covered[*column] = false);
+            }
+
+            excluded[prime] = true;
+            newly_excluded.push(prime);
+        }
+
+        newly_excluded.iter().for_each(|prime| excluded[*prime] = false);
+    }
+}
+
+// each uncovered minterm gives a sum of the primes that cover it, and the product of those sums
+// is multiplied out into a sum of products. every product is a way of covering all the
+// minterms, so the cheapest one is selected, and then the one with less primes. the expansion
+// grows exponentially with the cyclic core, so past MAX_PETRICK_PRODUCTS products the cover is
+// searched for by BranchAndBound instead.
+#[derive(Debug)]
+pub struct Petrick;
+
+impl CoverStrategy for Petrick {
+    fn name(&self) -> &'static str { "petrick" }
+
+    fn select(&self, incidence: &[Vec<bool>], costs: &[usize]) -> Result<CoverSelection> {
+        let Some((clauses, products)) = petrick_expansion(incidence)? else {
+            let mut selection = BranchAndBound.select(incidence, costs)?;
+            selection.explanation.insert(0, format!(
+                "The product of sums expands to more than {MAX_PETRICK_PRODUCTS} products, so \
+                the cheapest cover is searched for by branch and bound instead."
+            ));
+            return Ok(selection)
+        };
+
+        let primes = products.iter()
+            .min_by_key(|product| (cost_of(product, costs), product.len()))
+            .cloned()
+            .unwrap_or_default();
+
+        let mut explanation = Vec::new();
+        if ! clauses.is_empty() {
+            let label = |primes: &Vec<usize>, separator: &str| -> String {
+                primes.iter()
+                    .map(|prime| format!("P{prime}"))
+                    .collect::<Vec<String>>()
+                    .join(separator)
+            };
+            let clauses: Vec<String> = clauses.iter()
+                .map(|clause| {
+                    if clause.len() == 1 { label(clause, "") }
+                    else { format!("({})", label(clause, " + ")) }
+                })
+                .collect();
+            let products: Vec<String> = products.iter()
+                .map(|product| label(product, ""))
+                .collect();
+
+            explanation.push(format!("P = {}", clauses.join("")));
+            explanation.push(format!("  = {}", products.join(" + ")));
+            explanation.push(format!("The cheapest product is {}.", label(&primes, "")));
+        }
+
+        Ok(CoverSelection { primes, explanation })
+    }
+}
+
+// sets of primes indexes. depending on the context, a product of sums or a sum of products.
+pub type PrimesExpression = Vec<Vec<usize>>;
+
+// the most products the expansion of Petrick's method may hold at once.
+pub const MAX_PETRICK_PRODUCTS: usize = 1000;
+
+// returns the product of sums built from the minterms, and the sum of products it expands to.
+// both are kept free of redundant terms. when the expansion would hold more than
+// MAX_PETRICK_PRODUCTS products, it is given up and None is returned.
+pub fn petrick_expansion(incidence: &[Vec<bool>])
+    -> Result<Option<(PrimesExpression, PrimesExpression)>>
+{
+    let mut clauses: Vec<Vec<usize>> = Vec::new();
+    for minterm in 0..amount_of_columns(incidence) {
+        let clause: Vec<usize> = (0..incidence.len())
+            .filter(|prime| incidence[*prime][minterm])
+            .collect();
+
+        if clause.is_empty() { return Err(uncoverable(minterm)) }
+        clauses.push(clause);
+    }
+
+    // a sum holding every prime of another sum is always satisfied along with it.
+    let clauses = absorb(clauses);
+
+    let mut products: Vec<Vec<usize>> = vec![Vec::new()];
+    for clause in clauses.iter() {
+        let mut new_products = Vec::new();
+        for product in products.iter() {
+            // if the product already has a prime of this sum, adding any other one would
+            // only make it bigger.
+            if clause.iter().any(|prime| product.contains(prime)) {
+                new_products.push(product.clone());
+                continue
+            }
+
+            for prime in clause {
+                let mut new_product = product.clone();
+                new_product.push(*prime);
+                new_product.sort();
+                new_products.push(new_product);
+            }
+        }
+        // the products are bounded before absorbing them, since absorbing compares every pair.
+        if new_products.len() > MAX_PETRICK_PRODUCTS { return Ok(None) }
+        products = absorb(new_products);
+    }
+
+    Ok(Some((clauses, products)))
+}
+
+// removes duplicated sets and the ones that contain some other set, which are redundant both in
+// a product of sums and in a sum of products.
+fn absorb(mut sets: Vec<Vec<usize>>) -> Vec<Vec<usize>> {
+    sets.sort_by(|a, b| a.len().cmp(&b.len()).then(a.cmp(b)));
+    sets.dedup();
+
+    let mut kept: Vec<Vec<usize>> = Vec::new();
+    for set in sets {
+        let absorbed = kept.iter().any(|smaller| smaller.iter().all(|item| set.contains(item)));
+        if ! absorbed { kept.push(set) }
+    }
+
+    kept
+}
+
+fn max_coverage_cover(incidence: &[Vec<bool>], costs: &[usize]) -> Result<Vec<usize>> {
+    let amount_of_minterms = amount_of_columns(incidence);
+    let mut covered = vec![false; amount_of_minterms];
+    let mut primes = Vec::new();
+
+    while let Some(minterm) = covered.iter().position(|is_covered| ! is_covered) {
+        let (candidate, new_covertures) = (0..incidence.len())
+            .map(|prime| (prime, covers_how_many_uncovered(&incidence[prime], &covered)))
+            .rev()
+            .max_by_key(|(prime, new_covertures)| (*new_covertures, usize::MAX - costs[*prime]))
+            .ok_or_else(|| uncoverable(minterm))?;
+
+        if new_covertures == 0 { return Err(uncoverable(minterm)) }
+
+        select(candidate, &incidence[candidate], &mut covered, &mut primes);
+    }
+
+    // the minterms of an early pick may all end up covered by later ones, so such picks are
+    // dropped, starting from the most expensive ones.
+    let mut by_cost = primes.clone();
+    by_cost.sort_by_key(|prime| std::cmp::Reverse(costs[*prime]));
+    for candidate in by_cost {
+        let redundant = (0..amount_of_minterms)
+            .filter(|minterm| incidence[candidate][*minterm])
+            .all(|minterm| {
+                primes.iter().any(|other| *other != candidate && incidence[*other][minterm])
+            });
+        if redundant { primes.retain(|prime| *prime != candidate) }
+    }
+
+    Ok(primes)
+}
+
+// a cost that covering the uncovered minterms can not go below. minterms that share no candidate
+// prime need a different prime each, so the cheapest candidate of each of them is added up.
+// only the primes accepted by is_candidate may be chosen.
+fn lower_bound(
+    incidence: &Incidence,
+    costs: &[usize],
+    covered: &[bool],
+    is_candidate: impl Fn(usize) -> bool
+) -> usize {
+    let candidates_of = |minterm: usize| -> Vec<usize> {
+        incidence.primes_of[minterm].iter().copied().filter(|prime| is_candidate(*prime)).collect()
+    };
+    // minterms with less candidates block less of the others, so they are taken first.
+    let mut minterms: Vec<(usize, Vec<usize>)> = (0..covered.len())
+        .filter(|minterm| ! covered[*minterm])
+        .map(|minterm| (minterm, candidates_of(minterm)))
+        .collect();
+    minterms.sort_by_key(|(_, candidates)| candidates.len());
+
+    let mut blocked = covered.to_vec();
+    let mut bound = 0;
+
+    for (minterm, candidates) in minterms {
+        if blocked[minterm] { continue }
+
+        // a minterm without candidates can not be covered at all.
+        if candidates.is_empty() { return usize::MAX / 2 }
+
+        bound += candidates.iter().map(|prime| costs[*prime]).min().unwrap_or(0);
+        for prime in candidates {
+            incidence.minterms_of[prime].iter().for_each(|column| blocked[*column] = true);
+        }
+    }
+
+    bound
+}
+
+// the incidence matrix as lists, so that the searches only walk over the marks of a row or a
+// column: the primes covering each minterm, and the minterms covered by each prime.
+struct Incidence {
+    primes_of: Vec<Vec<usize>>,
+    minterms_of: Vec<Vec<usize>>
+}
+
+impl Incidence {
+    fn new(incidence: &[Vec<bool>]) -> Self {
+        let minterms_of: Vec<Vec<usize>> = incidence.iter()
+            .map(|row| (0..row.len()).filter(|column| row[*column]).collect())
+            .collect();
+        let mut primes_of = vec![Vec::new(); amount_of_columns(incidence)];
+        for (prime, minterms) in minterms_of.iter().enumerate() {
+            minterms.iter().for_each(|minterm| primes_of[*minterm].push(prime));
+        }
+
+        Incidence { primes_of, minterms_of }
+    }
+}
+
+fn select(prime: usize, row: &[bool], covered: &mut [bool], primes: &mut Vec<usize>) {
+    primes.push(prime);
+    row.iter().enumerate().for_each(|(minterm, covers)| if *covers { covered[minterm] = true });
+}
+
+fn covers_how_many_uncovered(row: &[bool], covered: &[bool]) -> usize {
+    row.iter().zip(covered.iter()).filter(|(covers, is_covered)| **covers && ! **is_covered).count()
+}
+
+fn cost_of(primes: &[usize], costs: &[usize]) -> usize {
+    primes.iter().map(|prime| costs[*prime]).sum()
+}
+
+fn amount_of_columns(incidence: &[Vec<bool>]) -> usize {
+    incidence.first().map_or(0, |row| row.len())
+}
+
+fn uncoverable(minterm: usize) -> QmError {
+    QmError::Internal(format!("found no prime implicant covering the uncovered minterm {minterm}"))
+}
@@ -1,164 +1,421 @@
 use prettytable::{Table, Row, Cell};
 use crate::implicant::Implicant;
+use crate::error::{QmError, Result};
+use crate::cover_strategy::{minimum_covers, BranchAndBound, CoverStrategy, FirstColumnGreedy};
 
+// each column of the map is a minterm of one of the outputs. a prime covers it when it implies
+// that output and covers the minterm. the map is kept both by rows, as the minterms covered by
+// each prime, and by columns, as the primes covering each minterm.
+#[derive(Clone)]
 pub struct CoverageMap {
-    map: Vec<Vec<bool>>,
+    rows: Vec<Bitset>,
+    columns: Vec<Bitset>,
     selected_primes: Vec<usize>,
     primes_names: Vec<String>,
     minterms_names: Vec<String>,
-    covered_minterms: Vec<usize>
+    minterms_outputs: Vec<usize>,
+    primes_costs: Vec<usize>,
+    // the same primes as selected_primes, and the minterms they cover.
+    selected: Bitset,
+    covered_minterms: Bitset,
+    // rows and columns dropped by the dominance reductions.
+    removed_primes: Bitset,
+    removed_minterms: Bitset,
+    // kept after choosing the remaining primes, so it can be printed.
+    cover_explanation: Vec<String>
+}
+
+// what a round of reductions over the coverage map did, as indexes of rows and columns.
+#[derive(Clone, Debug, Default)]
+pub struct ReductionRound {
+    pub dominating_minterms: Vec<usize>,
+    pub dominated_primes: Vec<usize>,
+    pub essentials: Vec<usize>
+}
+
+impl ReductionRound {
+    pub fn is_empty(&self) -> bool {
+        self.dominating_minterms.is_empty()
+            && self.dominated_primes.is_empty()
+            && self.essentials.is_empty()
+    }
 }
 
 impl CoverageMap {
-    pub fn new(prime_implicants: &Vec<Implicant>, basic_implicants: &Vec<Implicant>) -> Self {
-        let mut map = Vec::new();
+    // the basic implicants are the minterms that must be covered, tagged with the outputs for
+    // which they must be covered.
+    pub fn new(
+        prime_implicants: &[Implicant],
+        basic_implicants: &[Implicant],
+        output_names: &[String]
+    ) -> Result<Self> {
+        let multiple_outputs = output_names.len() > 1;
 
         let primes_names: Vec<String> = prime_implicants
             .iter()
-            .map(|p| p.get_string_representation())
-            .collect();
-        let minterms_names: Vec<String> = basic_implicants
-            .iter()
-            .map(|m| format!("m{}", m.minterm_number()))
+            .map(|p| {
+                if ! multiple_outputs { return p.get_string_representation() }
+                let implied_outputs: Vec<&str> = (0..output_names.len())
+                    .filter(|output| p.implies_output(*output))
+                    .map(|output| output_names[output].as_str())
+                    .collect();
+                format!("{} [{}]", p.get_string_representation(), implied_outputs.join(","))
+            })
             .collect();
 
-        for prime in prime_implicants {
-            let mut row = Vec::new();
-            for minterm in basic_implicants {
-                row.push(prime.covers(minterm));
+        let mut columns: Vec<(usize, &Implicant)> = Vec::new();
+        let mut minterms_names: Vec<String> = Vec::new();
+        for (output, output_name) in output_names.iter().enumerate() {
+            for minterm in basic_implicants.iter().filter(|m| m.implies_output(output)) {
+                let number = minterm.minterm_number()?;
+                minterms_names.push(
+                    if multiple_outputs { format!("{output_name}:m{number}") }
+                    else { format!("m{number}") }
+                );
+                columns.push((output, minterm));
+            }
+        }
+
+        let mut rows = vec![Bitset::new(columns.len()); prime_implicants.len()];
+        let mut covering_primes = vec![Bitset::new(prime_implicants.len()); columns.len()];
+        for (row, prime) in prime_implicants.iter().enumerate() {
+            for (column, (output, minterm)) in columns.iter().enumerate() {
+                if prime.implies_output(*output) && prime.covers(minterm) {
+                    rows[row].insert(column);
+                    covering_primes[column].insert(row);
+                }
             }
-            map.push(row);
         }
 
-        let selected_primes = Vec::new();
-        let covered_minterms = Vec::new();
+        let minterms_outputs = columns.iter().map(|(output, _)| *output).collect();
+        // primes cost their literals, unless other costs are set.
+        let primes_costs = prime_implicants.iter().map(|p| p.amount_of_literals()).collect();
 
-        CoverageMap { map, primes_names, minterms_names, selected_primes, covered_minterms }
+        Ok(CoverageMap {
+            selected: Bitset::new(rows.len()),
+            covered_minterms: Bitset::new(columns.len()),
+            removed_primes: Bitset::new(rows.len()),
+            removed_minterms: Bitset::new(columns.len()),
+            rows,
+            columns: covering_primes,
+            primes_names,
+            minterms_names,
+            minterms_outputs,
+            primes_costs,
+            selected_primes: Vec::new(),
+            cover_explanation: Vec::new()
+        })
     }
 
     // if some prime is the only who covers certain minterm, this prime is essential.
-    pub fn find_essentials(&mut self) -> usize {
+    pub fn find_essentials(&mut self) -> Result<usize> {
         let mut essentials_found: usize = 0;
 
         for column in 0..self.minterms_names.len() {
-            let mut covering_prime: Option<usize> = None;
-            let mut found_multiple_covering_primes = false;
-
-            for row in 0..self.primes_names.len() {
-                if self.map[row][column] {
-                    // had previously found another covering prime, so there is no
-                    // essential prime for this minterm.
-                    if covering_prime.is_some() { found_multiple_covering_primes = true }
-                    else { covering_prime = Some(row) }
-                }
-            }
+            let covering_primes: Vec<usize> = self.columns[column].iter().take(2).collect();
 
-            if ! found_multiple_covering_primes {
-                match covering_prime {
-                    Some(prime_index) => {
-                        if ! self.selected_primes.contains(&prime_index) {
-                            self.select_implicant(prime_index);
-                            essentials_found += 1;
-                        }
-                    },
-                    None => panic!(
-                        "Found no covering prime for {}, despite all minterms should be\
-                        covered at this point.", self.minterms_names[column]
-                    )
-                }
+            match (covering_primes.first().copied(), covering_primes.get(1)) {
+                (Some(prime_index), None) => {
+                    if ! self.selected.contains(prime_index) {
+                        self.select_implicant(prime_index);
+                        essentials_found += 1;
+                    }
+                },
+                // another covering prime was found, so there is no essential prime for this
+                // minterm.
+                (Some(_), Some(_)) => {},
+                (None, _) => return Err(QmError::Internal(format!(
+                    "found no covering prime for {}, despite all minterms should be \
+                    covered at this point", self.minterms_names[column]
+                )))
             }
         }
 
-        essentials_found
+        Ok(essentials_found)
     }
 
     fn select_implicant(&mut self, index: usize) {
-        if self.selected_primes.contains(&index) { return }
+        if self.selected.contains(index) { return }
         self.selected_primes.push(index);
+        self.selected.insert(index);
+        self.covered_minterms.union_with(&self.rows[index]);
+    }
+
+    // costs guide the reductions and the strategies choosing the remaining primes.
+    pub fn set_primes_costs(&mut self, costs: Vec<usize>) { self.primes_costs = costs }
+    pub fn get_primes_costs(&self) -> &Vec<usize> { &self.primes_costs }
+
+    pub fn prime_name(&self, index: usize) -> &str { &self.primes_names[index] }
+    pub fn minterm_name(&self, index: usize) -> &str { &self.minterms_names[index] }
+
+    // the primes neither selected nor removed, and the minterms neither covered nor removed.
+    fn active_primes(&self) -> Bitset {
+        Bitset::full(self.rows.len()).difference(&self.selected).difference(&self.removed_primes)
+    }
+
+    fn active_minterms(&self) -> Bitset {
+        Bitset::full(self.columns.len())
+            .difference(&self.covered_minterms)
+            .difference(&self.removed_minterms)
+    }
+
+    // after the essential primes are selected, the map can be further reduced by dominance:
+    // - a minterm whose covering primes include all the ones covering some other minterm is
+    //   removed, since covering the other one will cover it too;
+    // - a prime that covers only minterms covered by another prime, which is not more
+    //   expensive, is removed, since the other one can always replace it;
+    // - a minterm left with a single covering prime makes that prime essential.
+    // this method applies one round of these reductions. rounds should be repeated until one
+    // of them changes nothing, and what is left is the cyclic core of the map.
+    //
+    // dominance is only checked between rows or columns whose coverage can contain one another:
+    // the minterms that may dominate some minterm are the ones covered by every prime covering
+    // it, and the primes that may dominate some prime are the ones covering every minterm it
+    // covers. since dominance is transitive, removing what has any dominator gives the same
+    // result as removing one row or column at a time.
+    pub fn reduction_round(&mut self) -> Result<ReductionRound> {
+        let mut round = ReductionRound::default();
 
-        let minterms_covered: Vec<usize> = (0..self.map[index].len())
-            .filter(|minterm_index| self.map[index][*minterm_index])
+        let active_primes = self.active_primes();
+        let active_minterms = self.active_minterms();
+        let columns_coverage: Vec<Bitset> = self.columns.iter()
+            .map(|column| column.intersection(&active_primes))
             .collect();
 
-        for covered in minterms_covered {
-            if ! self.covered_minterms.contains(&covered) {
-                self.covered_minterms.push(covered);
+        let mut dominating_minterms = Bitset::new(self.columns.len());
+        for other in active_minterms.iter() {
+            let other_coverage = &columns_coverage[other];
+            let mut dominating = active_minterms.clone();
+            other_coverage.iter().for_each(|prime| dominating.intersect_with(&self.rows[prime]));
+
+            for minterm in dominating.iter().filter(|minterm| *minterm != other) {
+                // among columns covered by the same primes, only the first one is kept.
+                if other_coverage.count() < columns_coverage[minterm].count() || other < minterm {
+                    dominating_minterms.insert(minterm);
+                }
             }
         }
-    }
+        for minterm in dominating_minterms.iter() {
+            self.removed_minterms.insert(minterm);
+            round.dominating_minterms.push(minterm);
+        }
+
+        let active_minterms = self.active_minterms();
+        let rows_coverage: Vec<Bitset> = self.rows.iter()
+            .map(|row| row.intersection(&active_minterms))
+            .collect();
 
-    fn covers_how_many_uncovered(&self, implicant_index: usize) -> usize {
-        let mut covered_by_this_implicant = Vec::new();
-        self.map[implicant_index].iter()
-            .enumerate()
-            .for_each(|(index, covers)| if *covers { covered_by_this_implicant.push(index) });
+        for prime in active_primes.iter() {
+            let coverage = &rows_coverage[prime];
+            let cost = self.primes_costs[prime];
+            let mut dominating = active_primes.clone();
+            coverage.iter().for_each(|minterm| dominating.intersect_with(&self.columns[minterm]));
 
-        covered_by_this_implicant.iter()
-            .filter(|covered| ! self.covered_minterms.contains(covered))
-            .count()
+            let dominated = dominating.iter()
+                .filter(|other| *other != prime)
+                .any(|other| {
+                    let other_cost = self.primes_costs[other];
+                    // among rows with the same minterms and cost, only the first one is kept.
+                    let strictly_better = rows_coverage[other].count() > coverage.count()
+                        || other_cost < cost
+                        || other < prime;
+                    other_cost <= cost && strictly_better
+                });
+
+            if dominated || coverage.is_empty() {
+                self.removed_primes.insert(prime);
+                round.dominated_primes.push(prime);
+            }
+        }
+
+        let mut active_primes = self.active_primes();
+        for minterm in 0..self.minterms_names.len() {
+            if self.covered_minterms.contains(minterm) || self.removed_minterms.contains(minterm) {
+                continue
+            }
+
+            let coverage = self.columns[minterm].intersection(&active_primes);
+            let mut covering_primes = coverage.iter();
+            match (covering_primes.next(), covering_primes.next()) {
+                (None, _) => return Err(QmError::Internal(format!(
+                    "reductions left {} without covering primes", self.minterms_names[minterm]
+                ))),
+                (Some(prime), None) => {
+                    round.essentials.push(prime);
+                    self.select_implicant(prime);
+                    active_primes.remove(prime);
+                },
+                _ => {}
+            }
+        }
+
+        Ok(round)
     }
 
     // after running find_essentials, we can now choose which of the remaining primes will
     // be selected. for each uncovered minterm, we will choose one of the primes that covers
     // more yet uncovered minterms.
-    pub fn choose_remaining_primes(&mut self) {
-        for minterm_index in 0..self.minterms_names.len() {
-            if self.covered_minterms.contains(&minterm_index) { continue }
-
-            // implicants that cover the yet uncovered minterm
-            let prime_candidates_indexes: Vec<usize> = (0..self.primes_names.len())
-                .filter(|index| self.map[*index][minterm_index])
-                .collect();
-
-            // we will want to select the candidate who covers the largest number of yet
-            // uncovered implicants. if many options are available, we will just
-            // select the first one.
-
-            let candidates_and_new_covertures: Vec<(usize, usize)> = prime_candidates_indexes.iter()
-                .map(|prime_index| (*prime_index, self.covers_how_many_uncovered(*prime_index)))
-                .collect();
-
-            let max_new_covertures = candidates_and_new_covertures.iter()
-                .map(|(_, new_cov)| new_cov)
-                .max()
-                .expect("Failed to find implicant that covers more uncovered minterms.");
-
-            let candidate_to_be_selected: usize = candidates_and_new_covertures.iter()
-                .find(|(_, cand_new_cov)| *cand_new_cov == *max_new_covertures)
-                .expect("Failed to find implicant who had the max new covers.")
-                .0;
-
-            self.select_implicant(candidate_to_be_selected);
+    pub fn choose_remaining_primes(&mut self) -> Result<()> {
+        self.choose_remaining_primes_with(&FirstColumnGreedy)
+    }
+
+    // lets the strategy choose the primes that cover the minterms left uncovered.
+    // rows and columns removed by reductions are left out of the problem given to it.
+    pub fn choose_remaining_primes_with(&mut self, strategy: &dyn CoverStrategy) -> Result<()> {
+        let active_minterms: Vec<usize> = self.active_minterms().iter().collect();
+        let incidence: Vec<Vec<bool>> = (0..self.primes_names.len())
+            .map(|prime| {
+                let removed = self.removed_primes.contains(prime);
+                active_minterms.iter()
+                    .map(|minterm| ! removed && self.rows[prime].contains(*minterm))
+                    .collect()
+            })
+            .collect();
+
+        let selection = strategy.select(&incidence, &self.primes_costs)?;
+        for prime in selection.primes {
+            self.select_implicant(prime);
+        }
+
+        if let Some(minterm) = (0..self.minterms_names.len())
+            .find(|minterm| ! self.covered_minterms.contains(*minterm)) {
+            return Err(QmError::Internal(format!(
+                "the {} strategy left {} uncovered", strategy.name(), self.minterms_names[minterm]
+            )));
         }
+
+        self.cover_explanation = selection.explanation;
+
+        Ok(())
+    }
+
+    // the cost of the cheapest primes that cover the minterms left uncovered. the reductions
+    // always keep some cheapest cover, so it is searched for in the core of a copy of the map.
+    fn minimum_cost(&self) -> Result<usize> {
+        let mut core = self.clone();
+        while ! core.reduction_round()?.is_empty() {}
+        core.choose_remaining_primes_with(&BranchAndBound)?;
+
+        Ok(core.selected_primes.iter()
+            .filter(|prime| ! self.selected.contains(**prime))
+            .map(|prime| self.primes_costs[*prime])
+            .sum())
     }
 
     pub fn get_selected_implicants(&self) -> &Vec<usize> { &self.selected_primes }
 
+    // every cover of minimum cost that includes the primes selected so far, up to limit covers.
+    // it is meant to be called right after find_essentials: primes selected by reductions are
+    // not needed by every minimum cover, and would hide some of them.
+    pub fn minimum_covers(&self, limit: Option<usize>) -> Result<Vec<Vec<usize>>> {
+        let uncovered_minterms: Vec<usize> = (0..self.minterms_names.len())
+            .filter(|minterm| ! self.covered_minterms.contains(*minterm))
+            .collect();
+        let incidence: Vec<Vec<bool>> = self.rows.iter()
+            .map(|row| uncovered_minterms.iter().map(|minterm| row.contains(*minterm)).collect())
+            .collect();
+
+        let minimum_cost = self.minimum_cost()?;
+        let covers = minimum_covers(&incidence, &self.primes_costs, minimum_cost, limit)?;
+
+        Ok(covers.into_iter()
+            .map(|cover| {
+                let mut full_cover = self.selected_primes.clone();
+                full_cover.extend(cover);
+                full_cover.sort();
+                full_cover
+            })
+            .collect())
+    }
+
+    // the selected primes needed to build the expression of one output. a selected prime may
+    // imply an output whose minterms are all covered by other primes, and then it is left out.
+    pub fn get_output_implicants(&self, output: usize) -> Vec<usize> {
+        self.output_implicants_of(&self.selected_primes, output)
+    }
+
+    // same as get_output_implicants, for any cover of the map.
+    pub fn output_implicants_of(&self, cover: &[usize], output: usize) -> Vec<usize> {
+        let output_columns: Vec<usize> = (0..self.minterms_names.len())
+            .filter(|column| self.minterms_outputs[*column] == output)
+            .collect();
+
+        let mut implicants: Vec<usize> = cover.iter()
+            .copied()
+            .filter(|prime| output_columns.iter().any(|column| self.rows[*prime].contains(*column)))
+            .collect();
+        implicants.sort();
+
+        // drop implicants whose minterms are all covered by the other ones, starting from the
+        // ones that cover less minterms of this output.
+        let mut by_coverage = implicants.clone();
+        by_coverage.sort_by_key(|prime| {
+            output_columns.iter().filter(|column| self.rows[*prime].contains(**column)).count()
+        });
+        for candidate in by_coverage {
+            let redundant = output_columns.iter()
+                .filter(|column| self.rows[candidate].contains(**column))
+                .all(|column| implicants.iter()
+                    .any(|other| *other != candidate && self.rows[*other].contains(*column)));
+            if redundant { implicants.retain(|prime| *prime != candidate) }
+        }
+
+        implicants
+    }
+
+    // prints how the strategy chose the remaining primes, naming each prime after its row.
+    pub fn print_cover_explanation(&self) {
+        if self.cover_explanation.is_empty() { return }
+
+        for (prime, name) in self.primes_names.iter().enumerate() {
+            println!("P{prime} = {name}");
+        }
+        println!();
+        self.cover_explanation.iter().for_each(|line| println!("{line}"));
+    }
+
     pub fn print(&self) {
         let mut table = Table::new();
 
-        let header_vec: Vec<String> = vec![String::new()]
+        let header_vec: Vec<String> = [String::new()]
             .iter()
             .chain(self.minterms_names.iter())
             .cloned()
             .collect();
+        // minterms removed by reductions are printed in red.
         let header_row = Row::new(
             // style_spec("c") sets alignment to center
-            header_vec.iter().map(|c| Cell::new(c.as_str()).style_spec("c")).collect()
+            header_vec.iter()
+                .enumerate()
+                .map(|(column, c)| {
+                    let removed = column > 0 && self.removed_minterms.contains(column - 1);
+                    Cell::new(c.as_str()).style_spec(if removed { "cFr" } else { "c" })
+                })
+                .collect()
         );
         table.add_row(header_row);
 
         for (row_index, prime_name) in self.primes_names.iter().enumerate() {
             let mut new_row = Vec::new();
-            // if this minterm is selected, it will be printed in green
-            let row_spec = if self.selected_primes.contains(&row_index) { "Fg" } else { "" };
+            // if this minterm is selected, it will be printed in green. if it was removed by
+            // reductions, it is printed in red.
+            let removed_row = self.removed_primes.contains(row_index);
+            let row_spec = if self.selected.contains(row_index) { "Fg" }
+                else if removed_row { "Fr" }
+                else { "" };
             new_row.push(Cell::new(prime_name.as_str()).style_spec(row_spec));
 
-            for cover in &self.map[row_index] {
-                let new_cell = Cell::new(if *cover { "X" } else { " " })
-                    .style_spec("c")
-                    .style_spec(row_spec);
-                new_row.push(new_cell);
+            for column in 0..self.minterms_names.len() {
+                // covers in removed rows and columns are crossed out with a dash.
+                let removed = removed_row || self.removed_minterms.contains(column);
+                let mark = match (self.rows[row_index].contains(column), removed) {
+                    (true, false) => "X",
+                    (true, true) => "-",
+                    _ => " "
+                };
+                let cell_spec = if removed && row_spec.is_empty() { "cFr" }
+                    else { &format!("c{row_spec}") };
+                new_row.push(Cell::new(mark).style_spec(cell_spec));
             }
 
             table.add_row(Row::new(new_row));
@@ -167,3 +424,60 @@ impl CoverageMap {
         table.printstd();
     }
 }
+
+// a set of rows or columns of the map, with a bit for each one of them, so that sets are
+// intersected and compared a whole word at a time.
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct Bitset {
+    words: Vec<u64>
+}
+
+impl Bitset {
+    fn new(size: usize) -> Self {
+        Bitset { words: vec![0; size.div_ceil(64)] }
+    }
+
+    fn full(size: usize) -> Self {
+        let mut set = Bitset::new(size);
+        (0..size).for_each(|item| set.insert(item));
+        set
+    }
+
+    fn insert(&mut self, item: usize) { self.words[item / 64] |= 1 << (item % 64) }
+    fn remove(&mut self, item: usize) { self.words[item / 64] &= ! (1 << (item % 64)) }
+    fn contains(&self, item: usize) -> bool { self.words[item / 64] & 1 << (item % 64) != 0 }
+    fn count(&self) -> usize { self.words.iter().map(|word| word.count_ones() as usize).sum() }
+    fn is_empty(&self) -> bool { self.words.iter().all(|word| *word == 0) }
+
+    fn union_with(&mut self, other: &Bitset) {
+        self.words.iter_mut().zip(&other.words).for_each(|(word, other)| *word |= other);
+    }
+
+    fn intersect_with(&mut self, other: &Bitset) {
+        self.words.iter_mut().zip(&other.words).for_each(|(word, other)| *word &= other);
+    }
+
+    fn intersection(&self, other: &Bitset) -> Bitset {
+        let mut set = self.clone();
+        set.intersect_with(other);
+        set
+    }
+
+    fn difference(mut self, other: &Bitset) -> Bitset {
+        self.words.iter_mut().zip(&other.words).for_each(|(word, other)| *word &= ! other);
+        self
+    }
+
+    // the items of the set, in ascending order.
+    fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(index, word)| {
+            let mut rest = *word;
+            std::iter::from_fn(move || {
+                if rest == 0 { return None }
+                let bit = rest.trailing_zeros() as usize;
+                rest &= rest - 1;
+                Some(index * 64 + bit)
+            })
+        })
+    }
+}
@@ -0,0 +1,88 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+pub type Result<T> = std::result::Result<T, QmError>;
+
+#[derive(Debug)]
+pub enum QmError {
+    // reading or writing a file (or the terminal) failed.
+    Io { path: String, source: io::Error },
+    // a truth table cell holds something that is not a valid logic value.
+    // rows and columns are counted from 1, not counting the header line.
+    BadCell { row: usize, column: usize, value: String },
+    // the table does not have one row for each combination of its inputs.
+    MissingRows { expected: usize, found: usize },
+    // a row repeats the input combination of an earlier row, with the same outputs or not. for
+    // rows that are cubes, only different outputs are reported.
+    DuplicateRow { row: usize, first_row: usize, minterm: usize },
+    ConflictingRows { row: usize, first_row: usize, minterm: usize },
+    // a row does not correspond to the input combination expected at its position.
+    UnorderedRows { row: usize, expected: usize, found: usize },
+    // the header does not describe a usable table.
+    InvalidHeader(String),
+    // a row has a different amount of cells than the header.
+    WidthMismatch { row: usize, expected: usize, found: usize },
+    // a line of a file that is not a csv table could not be understood.
+    Syntax { line: usize, message: String },
+    // a function written in a single line of text could not be understood. the position counts
+    // characters from 1.
+    Notation { position: usize, message: String },
+    // a minterm given by its number does not fit the table, or is given twice with different
+    // values.
+    InvalidMinterm { minterm: usize, reason: String },
+    // some assumption of the algorithm did not hold. this indicates a bug.
+    Internal(String)
+}
+
+impl QmError {
+    pub fn io(path: &str, source: io::Error) -> Self {
+        QmError::Io { path: path.to_string(), source }
+    }
+}
+
+impl fmt::Display for QmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QmError::Io { path, source } => write!(f, "could not access '{path}': {source}"),
+            QmError::BadCell { row, column, value } => write!(
+                f, "invalid value '{value}' at row {row}, column {column}. Cells must be 0, 1, \
+                F, T or V. Input cells may also be a - standing for both values, and output cells \
+                may be a don't-care: X, -, D or *"
+            ),
+            QmError::MissingRows { expected, found } => write!(
+                f, "the table should have {expected} rows, but {found} were found"
+            ),
+            QmError::DuplicateRow { row, first_row, minterm } => write!(
+                f, "row {row} repeats input combination {minterm}, already given by row {first_row}"
+            ),
+            QmError::ConflictingRows { row, first_row, minterm } => write!(
+                f, "rows {first_row} and {row} overlap at input combination {minterm}, but give \
+                it different outputs"
+            ),
+            QmError::UnorderedRows { row, expected, found } => write!(
+                f, "row {row} holds input combination {found}, but {expected} was expected. \
+                Rows must be listed in ascending order"
+            ),
+            QmError::InvalidHeader(reason) => write!(f, "invalid header: {reason}"),
+            QmError::WidthMismatch { row, expected, found } => write!(
+                f, "row {row} has {found} cells, but the header declares {expected} columns"
+            ),
+            QmError::Syntax { line, message } => write!(f, "line {line}: {message}"),
+            QmError::Notation { position, message } => {
+                write!(f, "at character {position}: {message}")
+            }
+            QmError::InvalidMinterm { minterm, reason } => write!(f, "minterm {minterm} {reason}"),
+            QmError::Internal(reason) => write!(f, "internal error: {reason}")
+        }
+    }
+}
+
+impl Error for QmError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            QmError::Io { source, .. } => Some(source),
+            _ => None
+        }
+    }
+}
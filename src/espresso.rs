@@ -0,0 +1,362 @@
+use crate::truth_table::{OutputValue, TruthTable};
+use crate::implicant::{Implicant, VariablesNames};
+use crate::error::{QmError, Result};
+
+// the loop of reductions and expansions stops after this many passes even if it keeps improving.
+const MAX_PASSES: usize = 32;
+
+// a product term together with the outputs it is used for, in the same layout as the words of
+// an Implicant: bits of `mask` are the variables that do not matter, bits of `value` the ones
+// that must be true.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+struct Cube {
+    value: u64,
+    mask: u64,
+    outputs: u64
+}
+
+impl Cube {
+    fn contains(&self, other: &Cube) -> bool {
+        other.mask & self.mask == other.mask
+            && (other.value ^ self.value) & ! self.mask == 0
+            && other.outputs & self.outputs == other.outputs
+    }
+
+    // every minterm inside the cube, found by walking all subsets of its dashes.
+    fn minterms(&self) -> impl Iterator<Item = usize> + '_ {
+        let mut subset = Some(self.mask);
+        std::iter::from_fn(move || {
+            let current = subset?;
+            subset = if current == 0 { None } else { Some((current - 1) & self.mask) };
+            Some((self.value | current) as usize)
+        })
+    }
+}
+
+// one stage of the heuristic, with the cover it left behind.
+pub struct EspressoStep {
+    pub name: &'static str,
+    pub cover: Vec<Implicant>
+}
+
+// the cover found by the heuristic. every term of it is prime, and no term or output can be
+// dropped without leaving some minterm uncovered, but a cheaper cover may still exist.
+pub struct EspressoCover {
+    pub cover: Vec<Implicant>,
+    pub outputs_covers: Vec<Vec<Implicant>>,
+    pub steps: Vec<EspressoStep>
+}
+
+// the sets of the function, kept as one word of outputs for every row of the table.
+struct Function {
+    amount_of_variables: usize,
+    on_outputs: Vec<u64>,
+    off_outputs: Vec<u64>,
+    amount_of_outputs: usize
+}
+
+impl Function {
+    fn is_valid(&self, cube: &Cube) -> bool {
+        cube.minterms().all(|minterm| self.off_outputs[minterm] & cube.outputs == 0)
+    }
+
+    // how many times each one of the function is covered, for every output.
+    fn coverage_counts(&self, cover: &[Cube]) -> Vec<Vec<u32>> {
+        let mut counts = vec![vec![0u32; self.on_outputs.len()]; self.amount_of_outputs];
+        for cube in cover {
+            self.count(cube, &mut counts, 1);
+        }
+        counts
+    }
+
+    fn count(&self, cube: &Cube, counts: &mut [Vec<u32>], amount: i32) {
+        for minterm in cube.minterms() {
+            let covered = self.on_outputs[minterm] & cube.outputs;
+            for (output, output_counts) in counts.iter_mut().enumerate() {
+                if covered & (1 << output) != 0 {
+                    output_counts[minterm] = output_counts[minterm].saturating_add_signed(amount);
+                }
+            }
+        }
+    }
+}
+
+// minimizes the table by the Espresso-II heuristic. instead of generating every prime, a cover
+// is improved in a loop:
+// - EXPAND grows each term into a prime, and drops the terms that got covered by it;
+// - IRREDUNDANT drops the terms, or outputs of terms, that other terms already cover;
+// - REDUCE shrinks each term to the smallest one that still covers what only it covers, so the
+//   next expansion may grow it in another direction.
+// the loop stops once neither a pass nor a last gasp lowers the cost given by cost_of.
+pub fn espresso(
+    table: &TruthTable,
+    cost_of: &dyn Fn(&Implicant) -> usize
+) -> Result<EspressoCover> {
+    let amount_of_rows = table.input_rows().len();
+    if amount_of_rows != 1 << table.amount_of_variables() {
+        return Err(QmError::Internal(String::from(
+            "the espresso heuristic expects one row for each combination of inputs"
+        )));
+    }
+
+    let function = Function {
+        amount_of_variables: table.amount_of_variables(),
+        on_outputs: (0..amount_of_rows)
+            .map(|row| table.outputs_mask(row, OutputValue::True))
+            .collect(),
+        off_outputs: (0..amount_of_rows)
+            .map(|row| table.outputs_mask(row, OutputValue::False))
+            .collect(),
+        amount_of_outputs: table.amount_of_outputs()
+    };
+    let variables: VariablesNames = table.variables_names().into();
+    let to_implicants = |cover: &[Cube]| -> Vec<Implicant> {
+        cover.iter()
+            .map(|cube| Implicant::from_cube(&variables, cube.value, cube.mask, cube.outputs))
+            .collect()
+    };
+    let cost_of_cover = |cover: &[Cube]| -> (usize, usize) {
+        (to_implicants(cover).iter().map(cost_of).sum(), cover.len())
+    };
+
+    // the starting cover is made of the ones of the function.
+    let initial: Vec<Cube> = function.on_outputs.iter()
+        .enumerate()
+        .filter(|(_, outputs)| **outputs != 0)
+        .map(|(minterm, outputs)| Cube { value: minterm as u64, mask: 0, outputs: *outputs })
+        .collect();
+
+    let mut steps = Vec::new();
+    let mut cover = expand(&function, initial);
+    steps.push(EspressoStep { name: "EXPAND", cover: to_implicants(&cover) });
+    cover = irredundant(&function, cover);
+    steps.push(EspressoStep { name: "IRREDUNDANT", cover: to_implicants(&cover) });
+    let mut cost = cost_of_cover(&cover);
+
+    for _ in 0..MAX_PASSES {
+        let reduced = reduce(&function, cover.clone(), false);
+        let expanded = expand(&function, reduced.clone());
+        let candidate = irredundant(&function, expanded.clone());
+        let candidate_cost = cost_of_cover(&candidate);
+        if candidate_cost < cost {
+            steps.push(EspressoStep { name: "REDUCE", cover: to_implicants(&reduced) });
+            steps.push(EspressoStep { name: "EXPAND", cover: to_implicants(&expanded) });
+            steps.push(EspressoStep { name: "IRREDUNDANT", cover: to_implicants(&candidate) });
+            cover = candidate;
+            cost = candidate_cost;
+            continue
+        }
+
+        // as a last attempt, every term is reduced on its own and expanded again. the primes
+        // found this way are offered alongside the current cover, and IRREDUNDANT keeps the
+        // cheapest ones.
+        let mut candidate = cover.clone();
+        candidate.extend(expand(&function, reduce(&function, cover.clone(), true)));
+        let candidate = irredundant(&function, candidate);
+        let candidate_cost = cost_of_cover(&candidate);
+        if candidate_cost >= cost { break }
+
+        steps.push(EspressoStep { name: "LAST GASP", cover: to_implicants(&candidate) });
+        cover = candidate;
+        cost = candidate_cost;
+    }
+
+    // IRREDUNDANT may take outputs away from a term, and then the term can grow further on its
+    // inputs. growing it may leave other terms redundant, so both are repeated until no term
+    // grows. this ends, as every round that grows a term is left with fewer outputs or terms.
+    loop {
+        let grown = raise_inputs(&function, cover.clone());
+        if grown == cover { break }
+        cover = irredundant(&function, grown);
+        steps.push(EspressoStep { name: "EXPAND", cover: to_implicants(&cover) });
+    }
+
+    let implicants = to_implicants(&cover);
+    let outputs_covers = (0..function.amount_of_outputs)
+        .map(|output| {
+            implicants.iter()
+                .filter(|implicant| implicant.implies_output(output))
+                .cloned()
+                .collect()
+        })
+        .collect();
+
+    Ok(EspressoCover { cover: implicants, outputs_covers, steps })
+}
+
+fn expand(function: &Function, mut cover: Vec<Cube>) -> Vec<Cube> {
+    // variables that are already dashes in many terms are raised first, as they are the most
+    // likely to merge terms together.
+    let mut variables_order: Vec<u64> = (0..function.amount_of_variables)
+        .map(|position| 1u64 << position)
+        .collect();
+    variables_order.sort_by_key(|bit| {
+        std::cmp::Reverse(cover.iter().filter(|cube| cube.mask & bit != 0).count())
+    });
+
+    // bigger terms are expanded first, so they swallow the smaller ones.
+    cover.sort_by_key(|cube| std::cmp::Reverse(cube.mask.count_ones()));
+    let mut removed = vec![false; cover.len()];
+
+    for index in 0..cover.len() {
+        if removed[index] { continue }
+
+        let mut cube = cover[index];
+
+        // literals are raised starting from the ones that keep this term away from the most
+        // other terms, which differ from it in that single variable. raising them first makes
+        // this term swallow those terms, or at least get closer to them.
+        let mut scores = vec![(0usize, 0usize); function.amount_of_variables];
+        for (other, other_cube) in cover.iter().enumerate() {
+            if other == index || removed[other] || cube.outputs & other_cube.outputs == 0 {
+                continue
+            }
+
+            let conflicts = (cube.value ^ other_cube.value) & ! (cube.mask | other_cube.mask);
+            if conflicts.count_ones() != 1 { continue }
+
+            let raised = Cube {
+                value: cube.value & ! conflicts, mask: cube.mask | conflicts, ..cube
+            };
+            let score = &mut scores[conflicts.trailing_zeros() as usize];
+            if raised.contains(other_cube) { score.0 += 1 } else { score.1 += 1 }
+        }
+        let mut cube_order = variables_order.clone();
+        cube_order.sort_by_key(|bit| std::cmp::Reverse(scores[bit.trailing_zeros() as usize]));
+
+        for bit in cube_order {
+            if cube.mask & bit != 0 { continue }
+
+            // only the half that would be added needs to be checked.
+            let added_half = Cube { value: cube.value ^ bit, ..cube };
+            if function.is_valid(&added_half) {
+                cube.mask |= bit;
+                cube.value &= ! bit;
+            }
+        }
+
+        // the term may also be used for other outputs, as long as it covers some of their ones.
+        for output in 0..function.amount_of_outputs {
+            let bit = 1u64 << output;
+            if cube.outputs & bit != 0 { continue }
+
+            let raised = Cube { outputs: bit, ..cube };
+            let covers_some_one = cube.minterms()
+                .any(|minterm| function.on_outputs[minterm] & bit != 0);
+            if covers_some_one && function.is_valid(&raised) {
+                cube.outputs |= bit;
+            }
+        }
+
+        cover[index] = cube;
+        for other in 0..cover.len() {
+            if other != index && ! removed[other] && cube.contains(&cover[other]) {
+                removed[other] = true;
+            }
+        }
+    }
+
+    cover.into_iter()
+        .zip(removed)
+        .filter(|(_, removed)| ! removed)
+        .map(|(cube, _)| cube)
+        .collect()
+}
+
+// grows each term on its inputs only, keeping its outputs, and drops the terms it swallows.
+fn raise_inputs(function: &Function, mut cover: Vec<Cube>) -> Vec<Cube> {
+    let mut removed = vec![false; cover.len()];
+
+    for index in 0..cover.len() {
+        if removed[index] { continue }
+
+        let mut cube = cover[index];
+        for bit in (0..function.amount_of_variables).map(|position| 1u64 << position) {
+            if cube.mask & bit != 0 { continue }
+
+            let added_half = Cube { value: cube.value ^ bit, ..cube };
+            if function.is_valid(&added_half) {
+                cube.mask |= bit;
+                cube.value &= ! bit;
+            }
+        }
+
+        cover[index] = cube;
+        for other in 0..cover.len() {
+            if other != index && ! removed[other] && cube.contains(&cover[other]) {
+                removed[other] = true;
+            }
+        }
+    }
+
+    cover.into_iter()
+        .zip(removed)
+        .filter(|(_, removed)| ! removed)
+        .map(|(cube, _)| cube)
+        .collect()
+}
+
+fn irredundant(function: &Function, mut cover: Vec<Cube>) -> Vec<Cube> {
+    let mut counts = function.coverage_counts(&cover);
+
+    // smaller terms are the first ones to be dropped.
+    cover.sort_by_key(|cube| cube.mask.count_ones());
+
+    for cube in cover.iter_mut() {
+        for output in 0..function.amount_of_outputs {
+            let bit = 1u64 << output;
+            if cube.outputs & bit == 0 { continue }
+
+            let single_output = Cube { outputs: bit, ..*cube };
+            let is_redundant = single_output.minterms()
+                .filter(|minterm| function.on_outputs[*minterm] & bit != 0)
+                .all(|minterm| counts[output][minterm] > 1);
+            if is_redundant {
+                function.count(&single_output, &mut counts, -1);
+                cube.outputs &= ! bit;
+            }
+        }
+    }
+
+    cover.retain(|cube| cube.outputs != 0);
+    cover
+}
+
+// with independently, every term is reduced as if the others were left as they are. otherwise,
+// each term is reduced taking into account the reductions made before it.
+fn reduce(function: &Function, mut cover: Vec<Cube>, independently: bool) -> Vec<Cube> {
+    let mut counts = function.coverage_counts(&cover);
+    let all_variables = (1u64 << function.amount_of_variables) - 1;
+
+    cover.sort_by_key(|cube| std::cmp::Reverse(cube.mask.count_ones()));
+
+    for cube in cover.iter_mut() {
+        // the ones covered by this term alone, which the reduced term must still cover.
+        let mut outputs = 0u64;
+        let mut ones = 0u64;
+        let mut zeros = 0u64;
+        for minterm in cube.minterms() {
+            let only_here = (0..function.amount_of_outputs)
+                .map(|output| 1u64 << output)
+                .filter(|bit| cube.outputs & bit != 0 && function.on_outputs[minterm] & bit != 0)
+                .filter(|bit| counts[bit.trailing_zeros() as usize][minterm] == 1)
+                .fold(0u64, |outputs, bit| outputs | bit);
+            if only_here != 0 {
+                outputs |= only_here;
+                ones |= minterm as u64;
+                zeros |= ! (minterm as u64) & all_variables;
+            }
+        }
+
+        let mask = ones & zeros;
+        let reduced = Cube { value: ones & ! mask, mask, outputs };
+        if ! independently {
+            function.count(cube, &mut counts, -1);
+            function.count(&reduced, &mut counts, 1);
+        }
+        *cube = reduced;
+    }
+
+    cover.retain(|cube| cube.outputs != 0);
+    cover
+}
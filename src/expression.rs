@@ -0,0 +1,343 @@
+use crate::error::{QmError, Result};
+use crate::table_parser::{all_input_rows, default_output_names};
+use crate::truth_table::{OutputValue, TruthTable};
+
+// a boolean formula over the variables of a table, which are referred to by their position.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Expression {
+    Constant(bool),
+    Variable(usize),
+    Not(Box<Expression>),
+    And(Vec<Expression>),
+    Or(Vec<Expression>),
+    Xor(Vec<Expression>)
+}
+
+impl Expression {
+    // the value of the formula for a row of inputs.
+    pub fn evaluate(&self, row: &[bool]) -> bool {
+        match self {
+            Expression::Constant(value) => *value,
+            Expression::Variable(variable) => row[*variable],
+            Expression::Not(operand) => ! operand.evaluate(row),
+            Expression::And(operands) => operands.iter().all(|operand| operand.evaluate(row)),
+            Expression::Or(operands) => operands.iter().any(|operand| operand.evaluate(row)),
+            Expression::Xor(operands) => {
+                operands.iter().fold(false, |value, operand| value ^ operand.evaluate(row))
+            }
+        }
+    }
+}
+
+// parses a formula over the given variables. the operators are, from the loosest to the tightest:
+// - or, written as | or +;
+// - exclusive or, written as ^;
+// - and, written as & or *, or left implicit as in AB or A(B + C);
+// - not, written as ! before its operand or ' after it, as in !A or A'.
+// the constants 0 and 1 and parentheses are also accepted, so the expressions printed by this
+// tool, such as !AB + A!C or (A + !B)(!A + C), can be read back. variables are matched by their
+// names, preferring the longest one when several of them fit.
+pub fn parse_expression(text: &str, variables: &[String]) -> Result<Expression> {
+    let tokens = tokenize(text, 0, Some(variables))?;
+    Parser::new(tokens, text.chars().count(), variables).parse()
+}
+
+// a formula of a function, with its name when it was given one and the position where the
+// function starts.
+pub struct Function {
+    pub name: Option<String>,
+    pub position: usize,
+    pub expression: Expression
+}
+
+// parses one or more formulas over the given variables. several functions are separated by
+// semicolons or new lines, and each one may be named as in F = AB + C.
+pub fn parse_functions(text: &str, variables: &[String]) -> Result<Vec<Function>> {
+    split_functions(text)?.into_iter()
+        .map(|function| {
+            let tokens = tokenize(&function.formula, function.offset, Some(variables))?;
+            let end = function.offset + function.formula.chars().count();
+            let expression = Parser::new(tokens, end, variables).parse()?;
+            Ok(Function { name: function.name, position: function.start + 1, expression })
+        })
+        .collect()
+}
+
+// builds the table of one or more functions given as formulas, as read by parse_functions.
+// functions left unnamed are named F, or F0, F1 and so on. without the names of the variables,
+// each variable is a letter possibly followed by digits, such as A or x2, and they are ordered
+// by their names.
+pub fn table_from_expressions(text: &str, variables: Option<Vec<String>>) -> Result<TruthTable> {
+    let variables = match variables {
+        Some(variables) => variables,
+        None => {
+            let mut names: Vec<String> = Vec::new();
+            for function in split_functions(text)? {
+                for (_, token) in tokenize(&function.formula, function.offset, None)? {
+                    if let Token::Name(name) = token {
+                        if ! names.contains(&name) { names.push(name) }
+                    }
+                }
+            }
+            names.sort_by_key(|name| name_order(name));
+            names
+        }
+    };
+
+    let functions = parse_functions(text, &variables)?;
+    let default_names = default_output_names(functions.len());
+    let output_names = functions.iter()
+        .zip(default_names)
+        .map(|(function, default_name)| function.name.clone().unwrap_or(default_name))
+        .collect();
+
+    let inputs = all_input_rows(variables.len())?;
+    let outputs = inputs.iter()
+        .map(|row| {
+            functions.iter()
+                .map(|function| match function.expression.evaluate(row) {
+                    true => OutputValue::True,
+                    false => OutputValue::False
+                })
+                .collect()
+        })
+        .collect();
+
+    TruthTable::new(variables, output_names, inputs, outputs)
+}
+
+// the text of a function. start and offset are the amounts of characters that come before the
+// function and before its formula in the whole text.
+struct FunctionText {
+    name: Option<String>,
+    start: usize,
+    offset: usize,
+    formula: String
+}
+
+fn split_functions(text: &str) -> Result<Vec<FunctionText>> {
+    let mut functions: Vec<FunctionText> = Vec::new();
+    let mut start = 0;
+
+    for part in text.split([';', '\n']) {
+        let length = part.chars().count();
+        if ! part.trim().is_empty() {
+            let (name, offset, formula) = match part.split_once('=') {
+                Some((written_name, formula)) => {
+                    let name = written_name.trim();
+                    if name.is_empty() || name.contains(char::is_whitespace) {
+                        return Err(QmError::Notation {
+                            position: start + 1,
+                            message: format!("'{name}' is not a valid function name")
+                        });
+                    }
+                    let offset = start + written_name.chars().count() + 1;
+                    (Some(name.to_string()), offset, formula)
+                }
+                None => (None, start, part)
+            };
+            functions.push(FunctionText { name, start, offset, formula: formula.to_string() });
+        }
+        start += length + 1;
+    }
+
+    if functions.is_empty() {
+        return Err(QmError::Notation { position: 1, message: String::from("expected a formula") });
+    }
+
+    Ok(functions)
+}
+
+// names made of a letter and digits are ordered by their letter and then by their number, so
+// that X2 comes before X10.
+fn name_order(name: &str) -> (String, Option<u64>) {
+    let letters: String = name.chars().take_while(|symbol| ! symbol.is_ascii_digit()).collect();
+    let number = name[letters.len()..].parse().ok();
+    (letters, number)
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+enum Token { Name(String), Constant(bool), Not, Prime, And, Or, Xor, Open, Close }
+
+// splits a formula into tokens, each one with its position counting from 1. offset is the amount
+// of characters that come before the formula in the text it was taken from.
+fn tokenize(formula: &str, offset: usize, variables: Option<&[String]>)
+    -> Result<Vec<(usize, Token)>>
+{
+    let symbols: Vec<char> = formula.chars().collect();
+    let mut tokens: Vec<(usize, Token)> = Vec::new();
+    let mut next = 0;
+
+    while next < symbols.len() {
+        let position = offset + next + 1;
+        let symbol = symbols[next];
+        let token = match symbol {
+            _ if symbol.is_whitespace() => { next += 1; continue }
+            '!' => Token::Not,
+            '\'' => Token::Prime,
+            '&' | '*' => Token::And,
+            '|' | '+' => Token::Or,
+            '^' => Token::Xor,
+            '(' => Token::Open,
+            ')' => Token::Close,
+            '0' => Token::Constant(false),
+            '1' => Token::Constant(true),
+            _ if symbol.is_alphabetic() || symbol == '_' => {
+                let rest: String = symbols[next..].iter().collect();
+                let name = match variables {
+                    // the longest of the known names that starts here. an empty name would
+                    // match anywhere without moving forward, so it is never matched.
+                    Some(variables) => variables.iter()
+                        .filter(|name| ! name.is_empty() && rest.starts_with(name.as_str()))
+                        .max_by_key(|name| name.chars().count())
+                        .cloned()
+                        .ok_or_else(|| QmError::Notation {
+                            position, message: format!(
+                                "unknown variable at '{}'. The variables are {}",
+                                rest.split_whitespace().next().unwrap_or(""), variables.join(", ")
+                            )
+                        })?,
+                    None => std::iter::once(symbol)
+                        .chain(symbols[next + 1..].iter()
+                            .copied()
+                            .take_while(|symbol| symbol.is_ascii_digit()))
+                        .collect()
+                };
+                next += name.chars().count();
+                tokens.push((position, Token::Name(name)));
+                continue
+            }
+            _ => return Err(QmError::Notation {
+                position, message: format!("unexpected '{symbol}'")
+            })
+        };
+
+        tokens.push((position, token));
+        next += 1;
+    }
+
+    Ok(tokens)
+}
+
+// a recursive descent parser, with one method for each level of precedence.
+struct Parser<'a> {
+    tokens: Vec<(usize, Token)>,
+    next: usize,
+    // position just after the formula, where a missing operand is reported.
+    end: usize,
+    variables: &'a [String]
+}
+
+impl<'a> Parser<'a> {
+    fn new(tokens: Vec<(usize, Token)>, end: usize, variables: &'a [String]) -> Self {
+        Parser { tokens, next: 0, end: end + 1, variables }
+    }
+
+    fn parse(mut self) -> Result<Expression> {
+        let expression = self.or()?;
+        match self.tokens.get(self.next) {
+            None => Ok(expression),
+            Some((position, Token::Close)) => Err(QmError::Notation {
+                position: *position, message: String::from("this ) closes nothing")
+            }),
+            Some((position, _)) => Err(QmError::Notation {
+                position: *position, message: String::from("expected an operator")
+            })
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.next).map(|(_, token)| token)
+    }
+
+    fn eat(&mut self, expected: &Token) -> bool {
+        let found = self.peek() == Some(expected);
+        if found { self.next += 1 }
+        found
+    }
+
+    fn position(&self) -> usize {
+        self.tokens.get(self.next).map_or(self.end, |(position, _)| *position)
+    }
+
+    fn or(&mut self) -> Result<Expression> {
+        let mut operands = vec![self.xor()?];
+        while self.eat(&Token::Or) {
+            operands.push(self.xor()?);
+        }
+        Ok(if operands.len() == 1 { operands.remove(0) } else { Expression::Or(operands) })
+    }
+
+    fn xor(&mut self) -> Result<Expression> {
+        let mut operands = vec![self.and()?];
+        while self.eat(&Token::Xor) {
+            operands.push(self.and()?);
+        }
+        Ok(if operands.len() == 1 { operands.remove(0) } else { Expression::Xor(operands) })
+    }
+
+    // operands written one after the other are multiplied as well.
+    fn and(&mut self) -> Result<Expression> {
+        let mut operands = vec![self.not()?];
+        loop {
+            if self.eat(&Token::And) {
+                operands.push(self.not()?);
+                continue
+            }
+            match self.peek() {
+                Some(Token::Name(_) | Token::Constant(_) | Token::Not | Token::Open) => {
+                    operands.push(self.not()?);
+                }
+                _ => break
+            }
+        }
+        Ok(if operands.len() == 1 { operands.remove(0) } else { Expression::And(operands) })
+    }
+
+    fn not(&mut self) -> Result<Expression> {
+        if self.eat(&Token::Not) {
+            return Ok(Expression::Not(Box::new(self.not()?)))
+        }
+
+        let mut operand = self.primary()?;
+        while self.eat(&Token::Prime) {
+            operand = Expression::Not(Box::new(operand));
+        }
+        Ok(operand)
+    }
+
+    fn primary(&mut self) -> Result<Expression> {
+        let position = self.position();
+        let Some((_, token)) = self.tokens.get(self.next).cloned() else {
+            return Err(QmError::Notation {
+                position, message: String::from("expected a variable, a constant or (")
+            })
+        };
+        self.next += 1;
+
+        match token {
+            Token::Name(name) => {
+                let variable = self.variables.iter()
+                    .position(|variable| *variable == name)
+                    .ok_or_else(|| QmError::Notation {
+                        position, message: format!("unknown variable {name}")
+                    })?;
+                Ok(Expression::Variable(variable))
+            }
+            Token::Constant(value) => Ok(Expression::Constant(value)),
+            Token::Open => {
+                let expression = self.or()?;
+                if ! self.eat(&Token::Close) {
+                    return Err(QmError::Notation {
+                        position: self.position(),
+                        message: format!("expected ) to close the ( at character {position}")
+                    })
+                }
+                Ok(expression)
+            }
+            _ => Err(QmError::Notation {
+                position, message: String::from("expected a variable, a constant or (")
+            })
+        }
+    }
+}
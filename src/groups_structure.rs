@@ -1,17 +1,22 @@
+use std::collections::{HashMap, HashSet};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use crate::implicant::Implicant;
+use crate::error::{QmError, Result};
 
 pub struct GroupStructure {
     amount_of_groups: usize,
+    amount_of_outputs: usize,
     groups: Vec<Vec<Implicant>>
 }
 
 impl GroupStructure {
-    pub fn new(amount_of_variables: usize) -> Self {
+    pub fn new(amount_of_variables: usize, amount_of_outputs: usize) -> Self {
         let mut groups: Vec<Vec<Implicant>> = Vec::new();
         // +1 because a function with n variables can have n + 1 different amounts of true values
         (0..amount_of_variables + 1).for_each(|_| groups.push(Vec::new()));
 
-        GroupStructure { groups, amount_of_groups: amount_of_variables + 1 }
+        GroupStructure { groups, amount_of_groups: amount_of_variables + 1, amount_of_outputs }
     }
 
     pub fn add_implicant(&mut self, implicant: Implicant) {
@@ -22,74 +27,76 @@ impl GroupStructure {
     // will look for combinations for current groups.
     // it will create a new groups matrix and replace the previous one.
     // when there are no more possible combinations to make, it will return false.
-    pub fn combination_step(&mut self) -> bool {
-        let mut new_groups = Vec::new();
-
-        let mut old_groups_iterator = self.groups.iter().peekable();
+    pub fn combination_step(&mut self) -> Result<bool> {
+        let mut new_groups: Vec<Vec<Implicant>> = Vec::with_capacity(self.amount_of_groups);
         let mut found_some_combinable_this_step = false;
 
-        while let Some(old_group) = old_groups_iterator.next() {
-            let mut new_group = Vec::new();
-            let next_group_option = old_groups_iterator.peek();
-            if next_group_option.is_none() { break; }
-            let next_group = *next_group_option.unwrap();
-
-            for implicant in old_group.iter() {
-                let mut found_combinable = false;
-
-                for candidate in next_group.iter() {
-                    if implicant.check_if_combines(candidate) {
-                        found_combinable = true;
-                        found_some_combinable_this_step = true;
-                        let new_implicant = Implicant::from_implicants(implicant, candidate);
-
-                        // after step 3 of iteration, the same implicant start to appear
-                        // several times.
-                        if ! new_group.contains(&new_implicant) {
-                            new_group.push(new_implicant);
-                        }
-                    }
-                }
-
-                if ! found_combinable {
-                    let mut prime_marked_clone = implicant.clone();
-                    prime_marked_clone.mark_as_prime();
-                    new_group.push(prime_marked_clone);
-                }
+        // an implicant is absorbed when it combines into a bigger one that still implies all of
+        // its outputs. implicants that never get absorbed are primes.
+        let mut absorbed: Vec<Vec<bool>> = self.groups.iter()
+            .map(|group| vec![false; group.len()])
+            .collect();
+
+        // each pair of adjacent groups is combined on its own, so with the parallel feature the
+        // pairs are spread among threads. the results are gathered in the order of the groups,
+        // which keeps the outcome the same as when running on a single thread.
+        #[cfg(feature = "parallel")]
+        let pairs = (0..self.amount_of_groups - 1).into_par_iter();
+        #[cfg(not(feature = "parallel"))]
+        let pairs = 0..self.amount_of_groups - 1;
+
+        let combinations: Vec<PairCombination> = pairs
+            .map(|group_index| {
+                combine_groups(&self.groups[group_index], &self.groups[group_index + 1])
+            })
+            .collect::<Result<Vec<PairCombination>>>()?;
+
+        for (group_index, combination) in combinations.into_iter().enumerate() {
+            for (index, was_absorbed) in combination.absorbed.into_iter().enumerate() {
+                absorbed[group_index][index] |= was_absorbed;
+            }
+            for (index, was_absorbed) in combination.absorbed_next.into_iter().enumerate() {
+                absorbed[group_index + 1][index] |= was_absorbed;
             }
 
-            new_groups.push(new_group);
+            found_some_combinable_this_step |= ! combination.implicants.is_empty();
+            new_groups.push(combination.implicants);
         }
+        new_groups.push(Vec::new());
 
-        // last group never combines, but must me kept.
-        new_groups.push(self.groups.last().expect("Failed to load last group").clone());
-        match new_groups.last_mut().expect("Failed to load last group").get_mut(0) {
-            Some(implicant) => implicant.mark_as_prime(),
-            None => {}
+        // implicants that were not absorbed are kept in their groups, marked as primes.
+        for (group_index, group) in self.groups.iter().enumerate() {
+            for (implicant_index, implicant) in group.iter().enumerate() {
+                if absorbed[group_index][implicant_index] { continue }
+
+                let mut prime_marked_clone = implicant.clone();
+                prime_marked_clone.mark_as_prime();
+                new_groups[group_index].push(prime_marked_clone);
+            }
         }
 
         self.groups = new_groups;
 
-        found_some_combinable_this_step
+        Ok(found_some_combinable_this_step)
     }
 
-    pub fn extract_primes(&mut self) -> Vec<Implicant> {
+    pub fn extract_primes(&mut self) -> Result<Vec<Implicant>> {
         let mut primes = Vec::new();
 
         for group in self.groups.iter_mut() {
-            for index in (0..group.len()).into_iter().rev() {
+            for index in (0..group.len()).rev() {
                 if ! group[index].is_prime() {
-                    panic!("\
-                    Found an implicant that is not marked as prime. This method should\
-                    only be called after all combinations are made.\
-                    ");
+                    return Err(QmError::Internal(String::from(
+                        "found an implicant that is not marked as prime. Primes should only be \
+                        extracted after all combinations are made"
+                    )));
                 }
 
                 primes.push(group.remove(index));
             }
         }
 
-        primes
+        Ok(primes)
     }
 
     pub fn print_group(&self) {
@@ -101,10 +108,100 @@ impl GroupStructure {
             println!("{}", "-".repeat(row_length));
             print!("G{}", group);
             for implicant in &self.groups[group] {
+                // for tables with many outputs, the outputs each implicant implies are shown too.
+                let outputs_mark = if self.amount_of_outputs > 1 {
+                    format!(" [{}]", implicant.get_outputs_representation(self.amount_of_outputs))
+                } else { String::new() };
                 let prime_mark = if implicant.is_prime() { " *".to_string() } else { String::new() };
-                println!("\t{}{}", implicant.get_binary_representation(), prime_mark);
+                println!(
+                    "\t{}{}{}", implicant.get_binary_representation(), outputs_mark, prime_mark
+                );
             }
         }
         println!("{}", "-".repeat(row_length));
     }
-}
\ No newline at end of file
+}
+
+// the outcome of combining a group with the next one: the new implicants, and which implicants of
+// each group were absorbed by them.
+struct PairCombination {
+    implicants: Vec<Implicant>,
+    absorbed: Vec<bool>,
+    absorbed_next: Vec<bool>
+}
+
+fn combine_groups(group: &[Implicant], next_group: &[Implicant]) -> Result<PairCombination> {
+    let mut combination = PairCombination {
+        implicants: Vec::new(),
+        absorbed: vec![false; group.len()],
+        absorbed_next: vec![false; next_group.len()]
+    };
+    let next_group_buckets = bucket_by_cube(next_group);
+
+    // after step 3 of iteration, the same implicant start to appear several times, so the ones
+    // already made are remembered.
+    let mut already_made: HashSet<Implicant> = HashSet::new();
+
+    for (implicant_index, implicant) in group.iter().enumerate() {
+        if implicant.is_prime() { continue }
+
+        for candidate_index in combination_candidates(implicant, &next_group_buckets) {
+            let candidate = &next_group[candidate_index];
+            if ! implicant.check_if_combines(candidate) { continue }
+
+            let new_implicant = Implicant::from_implicants(implicant, candidate)?;
+
+            if new_implicant.outputs() == implicant.outputs() {
+                combination.absorbed[implicant_index] = true;
+            }
+            if new_implicant.outputs() == candidate.outputs() {
+                combination.absorbed_next[candidate_index] = true;
+            }
+
+            if ! already_made.contains(&new_implicant) {
+                already_made.insert(new_implicant.clone());
+                combination.implicants.push(new_implicant);
+            }
+        }
+    }
+
+    Ok(combination)
+}
+
+// indexes of the implicants of a group that are not primes yet, bucketed by their dash mask and
+// value. since implicants only combine with others that have dashes in the same places, this
+// lets each implicant look up its partners instead of being compared with the whole group.
+fn bucket_by_cube(group: &[Implicant]) -> HashMap<(u64, u64), Vec<usize>> {
+    let mut buckets: HashMap<(u64, u64), Vec<usize>> = HashMap::new();
+
+    for (index, implicant) in group.iter().enumerate() {
+        if implicant.is_prime() { continue }
+        buckets.entry((implicant.mask(), implicant.value())).or_default().push(index);
+    }
+
+    buckets
+}
+
+// the implicants of the next group that may combine with the given one: those with the same
+// dash mask whose value has exactly one more true variable. they are given in the order they
+// appear in the group, so the result does not depend on how the buckets are hashed.
+fn combination_candidates(
+    implicant: &Implicant,
+    next_group_buckets: &HashMap<(u64, u64), Vec<usize>>
+) -> Vec<usize> {
+    let amount_of_variables = implicant.amount_of_variables();
+    let free_bits = ! (implicant.mask() | implicant.value());
+    let mut candidates: Vec<usize> = Vec::new();
+
+    for bit in (0..amount_of_variables).map(|position| 1u64 << position) {
+        if free_bits & bit == 0 { continue }
+
+        let key = (implicant.mask(), implicant.value() | bit);
+        if let Some(indexes) = next_group_buckets.get(&key) {
+            candidates.extend(indexes);
+        }
+    }
+
+    candidates.sort_unstable();
+    candidates
+}
@@ -1,195 +1,208 @@
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
 use crate::table_parser::convert_boolean_row_to_number;
-
-#[derive(Copy, Clone)]
-enum LogicLoad { False, True, DontMatter }
-
-impl LogicLoad {
-    fn is_true(&self) -> bool { match self { Self::True => true, _ => false } }
-    fn dont_matter(&self) -> bool { match self { Self::DontMatter => true, _ => false } }
-    fn is_false(&self) -> bool { match self { Self::False => true, _ => false } }
-    fn equals(&self, other: &LogicLoad) -> bool {
-        let both_true = self.is_true() && other.is_true();
-        let both_dont_matter = self.dont_matter() && other.dont_matter();
-        let both_false = self.is_false() && other.is_false();
-        both_true || both_dont_matter || both_false
-    }
-}
-
-#[derive(Clone)]
-struct MintermFragment {
-    variable_name: String,
-    logic_load: LogicLoad
-}
-
-impl MintermFragment {
-    fn get_string_representation(&self) -> String {
-        match self.logic_load {
-            LogicLoad::False => format!("!{}", self.variable_name),
-            LogicLoad::DontMatter => String::new(),
-            LogicLoad::True => self.variable_name.to_string()
-        }
-    }
-
-    fn get_binary_representation(&self) -> String {
-        match self.logic_load {
-            LogicLoad::False => String::from("0"),
-            LogicLoad::DontMatter => String::from("-"),
-            LogicLoad::True => String::from("1")
-        }
-    }
-}
-
+use crate::error::{QmError, Result};
+
+// implicants are packed into machine words, so tables may have at most this amount of inputs.
+// one bit is kept spare so that the amount of rows, 2^n, still fits in a word.
+pub const MAX_VARIABLES: usize = 63;
+
+// the names of the variables are shared among all implicants of a table instead of being
+// copied into each one of them.
+pub type VariablesNames = Arc<[String]>;
+
+// an implicant is stored as a pair of words. bit i of `mask` is set when the variable that is
+// i positions away from the last one does not matter, and otherwise bit i of `value` tells
+// whether that variable is true or negated. this way the value of a minterm is its number.
+//
+// besides its product term, an implicant is tagged with the outputs it implies: bit i of the
+// outputs mask is set when the implicant belongs to the i-th output of the table. this lets
+// a single product term be shared among several outputs.
 #[derive(Clone)]
 pub struct Implicant {
-    variables_names: Vec<String>,
-    fragments: Vec<MintermFragment>,
+    variables_names: VariablesNames,
+    value: u64,
+    mask: u64,
+    outputs: u64,
     marked_as_prime: bool
 }
 
 impl Implicant {
     pub fn is_prime(&self) -> bool { self.marked_as_prime }
     pub fn mark_as_prime(&mut self) { self.marked_as_prime = true; }
+    pub fn outputs(&self) -> u64 { self.outputs }
+    pub fn implies_output(&self, output: usize) -> bool { self.outputs & (1 << output) != 0 }
+    pub fn value(&self) -> u64 { self.value }
+    pub fn mask(&self) -> u64 { self.mask }
+    pub fn variables_names(&self) -> &[String] { &self.variables_names }
+    pub fn amount_of_variables(&self) -> usize { self.variables_names.len() }
 
     // creates the first implicants, which are minterms built from input rows.
-    pub fn from_input(row_of_inputs: &Vec<bool>, variables_names: Vec<String>) -> Self {
-        let mut fragments = Vec::new();
-
-        for (index, value) in row_of_inputs.iter().enumerate() {
-            let logic_load = if *value { LogicLoad::True } else { LogicLoad::False };
-            fragments.push(
-                MintermFragment {
-                    variable_name: variables_names[index].to_string(),
-                    logic_load
-                }
-            );
+    pub fn from_input(row_of_inputs: &[bool], variables_names: &VariablesNames, outputs: u64)
+        -> Self
+    {
+        Implicant {
+            variables_names: Arc::clone(variables_names),
+            value: convert_boolean_row_to_number(row_of_inputs) as u64,
+            mask: 0,
+            outputs,
+            marked_as_prime: false
         }
-
-        Implicant { variables_names, fragments, marked_as_prime: false }
     }
 
-    pub fn from_implicants(impl_a: &Implicant, impl_b: &Implicant) -> Self {
-        let mut new_fragments = Vec::new();
-        let mut unequal_fragments_found: usize = 0;
-
-        for (frag_a, frag_b) in impl_a.fragments.iter().zip(impl_b.fragments.iter()) {
-            let variable_name = frag_a.variable_name.to_string();
+    // creates an implicant straight from its words, as described above.
+    pub fn from_cube(variables_names: &VariablesNames, value: u64, mask: u64, outputs: u64)
+        -> Self
+    {
+        Implicant {
+            variables_names: Arc::clone(variables_names),
+            value: value & ! mask,
+            mask,
+            outputs,
+            marked_as_prime: false
+        }
+    }
 
-            if frag_a.logic_load.equals(&frag_b.logic_load) {
-                let logic_load = frag_a.logic_load;
-                new_fragments.push(MintermFragment { variable_name, logic_load });
-            }
+    pub fn from_implicants(impl_a: &Implicant, impl_b: &Implicant) -> Result<Self> {
+        let difference = impl_a.value ^ impl_b.value;
 
-            else {
-                unequal_fragments_found += 1;
-                new_fragments.push(
-                    MintermFragment { variable_name, logic_load: LogicLoad::DontMatter }
-                );
-            }
+        if impl_a.mask != impl_b.mask || difference.count_ones() != 1 {
+            return Err(QmError::Internal(format!(
+                "trying to combine incompatible implicants {} and {}",
+                impl_a.get_binary_representation(), impl_b.get_binary_representation()
+            )));
         }
 
-        if unequal_fragments_found != 1 { panic!("Trying to combine incompatible implicants.") }
-
-        Implicant {
-            variables_names: impl_a.variables_names.clone(),
-            fragments: new_fragments,
+        // the combination only implies the outputs that both halves imply.
+        Ok(Implicant {
+            variables_names: Arc::clone(&impl_a.variables_names),
+            value: impl_a.value & ! difference,
+            mask: impl_a.mask | difference,
+            outputs: impl_a.outputs & impl_b.outputs,
             marked_as_prime: false,
-        }
+        })
     }
 
-    pub fn clone(&self) -> Self {
-        Self {
-            variables_names: self.variables_names.clone(),
-            fragments: self.fragments.clone(),
-            marked_as_prime: false,
-        }
+    // bit of the variable at the given position, counting from the first one.
+    fn bit_of(&self, variable: usize) -> u64 {
+        1 << (self.variables_names.len() - 1 - variable)
     }
 
     pub fn amount_of_true_variables(&self) -> usize {
-        self.fragments.iter()
-            .filter(|frag| match frag.logic_load { LogicLoad::True => true, _ => false })
-            .count()
+        self.value.count_ones() as usize
+    }
+
+    // amount of variables that appear in this implicant, negated or not.
+    pub fn amount_of_literals(&self) -> usize {
+        self.variables_names.len() - self.mask.count_ones() as usize
+    }
+
+    // the variables that appear in this implicant, telling whether each one is true or negated.
+    pub fn literals(&self) -> Vec<(&str, bool)> {
+        (0..self.variables_names.len())
+            .filter(|variable| self.mask & self.bit_of(*variable) == 0)
+            .map(|variable| (
+                self.variables_names[variable].as_str(),
+                self.value & self.bit_of(variable) != 0
+            ))
+            .collect()
     }
 
     // for implicants that are minterms, this method returns its associated number
     // this number is the index of its corresponding row on the truth table (if it starts from
     // least significative inputs towards the most significatives ones)
-    pub fn minterm_number(&self) -> usize {
-        let mut logic_loads = Vec::new();
-
-        for variable in &self.fragments {
-            match variable.logic_load {
-                LogicLoad::True => logic_loads.push(true),
-                LogicLoad::False => logic_loads.push(false),
-                LogicLoad::DontMatter => panic!(
-                    "This implicant is not equivalent to a minterm.\
-                    This method can only be called over minterms."
-                )
-            }
+    pub fn minterm_number(&self) -> Result<usize> {
+        if self.mask != 0 {
+            return Err(QmError::Internal(format!(
+                "implicant {} is not a minterm, so it has no minterm number",
+                self.get_binary_representation()
+            )));
         }
 
-        convert_boolean_row_to_number(logic_loads.as_slice())
+        Ok(self.value as usize)
     }
 
+    // two implicants combine when they have dashes in the same places and differ in exactly
+    // one of the remaining variables.
     pub fn check_if_combines(&self, other: &Implicant) -> bool {
         if self.marked_as_prime || other.marked_as_prime { return false; }
+        if self.outputs & other.outputs == 0 { return false; }
 
-        let mut differences: usize = 0;
-        for (self_var, other_var) in self.fragments.iter().zip(other.fragments.iter()) {
-            match (&self_var.logic_load, &other_var.logic_load) {
-                (LogicLoad::True, LogicLoad::True) => {},
-                (LogicLoad::DontMatter, LogicLoad::DontMatter) => {},
-                (LogicLoad::False, LogicLoad::False) => {},
-                _ => { differences += 1; }
-            }
-        }
-
-        differences == 1
+        self.mask == other.mask && (self.value ^ other.value).count_ones() == 1
     }
 
     // check if other implicant may be logically covered by self.
     pub fn covers(&self, other: &Implicant) -> bool {
-        for (self_var, other_var) in self.fragments.iter().zip(other.fragments.iter()) {
-            let this_matches = match (self_var.logic_load, other_var.logic_load) {
-                (LogicLoad::True, LogicLoad::False) => false,
-                (LogicLoad::False, LogicLoad::True) => false,
-                _ => true
-            };
-
-            if ! this_matches { return false }
-        }
+        let cared_by_both = ! (self.mask | other.mask);
+        (self.value ^ other.value) & cared_by_both == 0
+    }
 
-        true
+    // check if the minterm with the given number is inside this implicant.
+    pub fn covers_minterm(&self, minterm: u64) -> bool {
+        (self.value ^ minterm) & ! self.mask == 0
     }
 
     pub fn get_string_representation(&self) -> String {
         let mut rep = String::new();
 
-        for frag in &self.fragments {
-            rep.push_str(frag.get_string_representation().as_str());
+        for (name, is_true) in self.literals() {
+            if ! is_true { rep.push('!') }
+            rep.push_str(name);
         }
 
+        // an implicant where no variable matters is constant true.
+        if rep.is_empty() { rep.push('1') }
+
         rep
     }
 
-    pub fn get_binary_representation(&self) -> String {
-        let mut rep = String::new();
-
-        for frag in &self.fragments {
-            rep.push_str(frag.get_binary_representation().as_str());
+    // for implicants built from the zeros of a function, this is the sum term that is false
+    // exactly where the implicant holds. for example, 0-1 becomes (A + !C).
+    pub fn get_clause_representation(&self) -> String {
+        let literals: Vec<String> = self.literals().into_iter()
+            .map(|(name, is_true)| if is_true { format!("!{name}") } else { name.to_string() })
+            .collect();
+
+        match literals.len() {
+            // a clause where no variable matters is constant false.
+            0 => String::from("0"),
+            1 => literals[0].clone(),
+            _ => format!("({})", literals.join(" + "))
         }
+    }
 
-        rep
+    pub fn get_binary_representation(&self) -> String {
+        (0..self.variables_names.len())
+            .map(|variable| {
+                let bit = self.bit_of(variable);
+                if self.mask & bit != 0 { '-' }
+                else if self.value & bit != 0 { '1' }
+                else { '0' }
+            })
+            .collect()
+    }
+
+    // the outputs implied by this implicant, as a string of 0s and 1s starting from the first
+    // output.
+    pub fn get_outputs_representation(&self, amount_of_outputs: usize) -> String {
+        (0..amount_of_outputs)
+            .map(|output| if self.implies_output(output) { '1' } else { '0' })
+            .collect()
     }
 }
 
 impl PartialEq for Implicant {
     fn eq(&self, other: &Self) -> bool {
-        for (self_var, other_var) in self.fragments.iter().zip(other.fragments.iter()) {
-            if ! self_var.logic_load.equals(&other_var.logic_load) {
-                return false;
-            }
-        }
+        self.outputs == other.outputs && self.mask == other.mask && self.value == other.value
+    }
+}
+
+impl Eq for Implicant {}
 
-        true
+impl Hash for Implicant {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+        self.mask.hash(state);
+        self.outputs.hash(state);
     }
 }
@@ -0,0 +1,32 @@
+// Quine-McCluskey boolean function minimization.
+//
+// the minimizer can be driven through `minimize` or `minimize_with`, which compute silently and
+// return a `Minimization`, or through `algorithm`, which prints every stage of the process as it
+// goes. `Options` selects how the minimization is carried out, including whether the exact
+// Quine-McCluskey method or the Espresso heuristic is used.
+
+pub mod table_parser;
+pub mod implicant;
+pub mod truth_table;
+pub mod algorithm;
+pub mod groups_structure;
+pub mod coverage_map;
+pub mod error;
+pub mod cover_strategy;
+pub mod cost_model;
+pub mod espresso;
+pub mod pla;
+pub mod blif;
+pub mod minterm_list;
+pub mod expression;
+pub mod verifier;
+pub mod verilog;
+
+pub use truth_table::{InputFormat, OutputValue, TruthTable};
+pub use implicant::Implicant;
+pub use groups_structure::GroupStructure;
+pub use coverage_map::CoverageMap;
+pub use error::{QmError, Result};
+pub use algorithm::{algorithm, minimize, minimize_with, Engine, Form, Minimization, Options};
+pub use cover_strategy::{CoverSelection, CoverStrategy};
+pub use cost_model::{CostMetric, CostModel};
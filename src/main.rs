@@ -1,79 +1,441 @@
-mod table_parser;
-mod implicant;
-mod truth_table;
-mod algorithm;
-mod groups_structure;
-mod coverage_map;
-
-use truth_table::TruthTable;
-use algorithm::algorithm;
+use quine_mccluskey::{
+    algorithm, minimize_with, CostMetric, Engine, Form, InputFormat, Minimization, Options, OutputValue, QmError,
+    TruthTable
+};
+use quine_mccluskey::cover_strategy::{available_strategies, strategy_by_name};
+use quine_mccluskey::cost_model::MAX_WEIGHT;
+use quine_mccluskey::pla::write_pla;
+use quine_mccluskey::blif::write_blif;
+use quine_mccluskey::verilog::write_verilog;
+use quine_mccluskey::expression::table_from_expressions;
+use quine_mccluskey::verifier::verify_formula;
+use quine_mccluskey::minterm_list::{
+    parse_minterm_notation, parse_names, parse_numbers, table_from_lists
+};
 use std::env;
 use std::fs;
+use std::path::Path;
 use std::process;
 
 fn main() {
-    let mut args = env::args();
-    args.next(); // discard program name arg
-    let provided_filepath = args.next();
-
-    let first_argument = match provided_filepath {
-        Some(path) => path,
-        None => panic!("Please, provide a filepath to a truth table (has to be .csv). Consider \
-        consulting the help section with quine-mccluskey --help.")
-    };
+    if let Err(error) = run() {
+        eprintln!("Error: {error}.");
+        process::exit(1);
+    }
+}
 
-    // if called, this function terminates the program.
-    if ["--help", "-h"].contains(&first_argument.as_str()) { print_help() }
+fn run() -> Result<(), String> {
+    let mut args = env::args().skip(1).peekable();
+    if args.next_if(|arg| arg == "verify").is_some() { return run_verify(args) }
 
-    let filepath = first_argument.as_str();
+    // the table may be given by its file, by the numbers of its ones with --minterms, or by a
+    // formula with --expr.
+    let filepath = args.next_if(|arg| ! arg.starts_with('-'));
 
     let mut step_by_step = false;
-    let mut dump_directory: Option<String> = None;
+    let mut quiet = false;
+    let mut dump_path: Option<String> = None;
+    let mut dump_format: Option<DumpFormat> = None;
+    let mut emit_format: Option<DumpFormat> = None;
+    let mut reading = ReadingOptions::default();
+    let mut minterms: Option<String> = None;
+    let mut dont_cares: Option<Vec<usize>> = None;
+    let mut variables: Option<Vec<String>> = None;
+    let mut expression: Option<String> = None;
+    let mut options = Options::default();
 
     // iterate over optional args provided
     while let Some(arg) = args.next() {
+        if reading.parse(&arg, &mut args)? { continue }
+
         match arg.as_str() {
             "--step-by-step" => { step_by_step = true },
+            "--quiet" | "-q" => { quiet = true },
             "--help" | "-h" => print_help(),
+            "--minterms" | "-m" => {
+                minterms = Some(
+                    args.next().ok_or("expected a list of minterms such as 0,2,5 after --minterms")?
+                );
+            }
+            "--dont-cares" => {
+                let list = args.next()
+                    .ok_or("expected a list of minterms such as 10,11 after --dont-cares")?;
+                dont_cares = Some(parse_numbers(&list).map_err(|error| error.to_string())?);
+            }
+            "--expr" => {
+                expression = Some(
+                    args.next().ok_or("expected a formula such as AB + !C after --expr")?
+                );
+            }
+            "--vars" => {
+                variables = Some(parse_names(
+                    &args.next().ok_or("expected the names of the variables after --vars")?
+                ));
+            }
+            "--form" | "-f" => {
+                options.form = match args.next().as_deref() {
+                    Some("sop") => Form::SumOfProducts,
+                    Some("pos") => Form::ProductOfSums,
+                    Some("best") => Form::Best,
+                    _ => return Err(String::from("expected sop, pos or best after --form"))
+                };
+            }
+            "--engine" | "-e" => {
+                options.engine = args.next()
+                    .and_then(|name| Engine::from_name(&name))
+                    .ok_or("expected qm, espresso or auto after --engine")?;
+            }
+            "--espresso-threshold" => {
+                options.espresso_threshold = args.next()
+                    .and_then(|amount| amount.parse().ok())
+                    .ok_or("expected an amount of variables after --espresso-threshold")?;
+            }
+            "--cover" | "-c" => {
+                options.cover = args.next()
+                    .and_then(|name| strategy_by_name(&name))
+                    .ok_or_else(|| format!(
+                        "expected one of {} after --cover", strategies_names().join(", ")
+                    ))?;
+            }
+            "--cost" => {
+                options.cost_model.metric = args.next()
+                    .and_then(|name| CostMetric::from_name(&name))
+                    .ok_or("expected literals, terms or gate-inputs after --cost")?;
+            }
+            "--complemented-weight" => {
+                options.cost_model.complemented_weight = args.next()
+                    .and_then(|weight| weight.parse().ok())
+                    .filter(|weight| *weight <= MAX_WEIGHT)
+                    .ok_or_else(|| format!(
+                        "expected a weight of at most {MAX_WEIGHT} for complemented literals \
+                        after --complemented-weight"
+                    ))?;
+            }
+            "--weight" | "-w" => {
+                let (variable, weight) = args.next()
+                    .and_then(|assignment| {
+                        let (variable, weight) = assignment.split_once('=')?;
+                        Some((variable.to_string(), weight.parse().ok()?))
+                    })
+                    .filter(|(_, weight)| *weight <= MAX_WEIGHT)
+                    .ok_or_else(|| format!(
+                        "expected a variable weight of at most {MAX_WEIGHT}, such as A=2, after \
+                        --weight"
+                    ))?;
+                options.cost_model.variables_weights.insert(variable, weight);
+            }
+            "--all-solutions" | "-a" => { options.all_solutions = true },
+            "--max-solutions" => {
+                options.all_solutions = true;
+                options.max_solutions = Some(
+                    args.next()
+                        .and_then(|amount| amount.parse().ok())
+                        .ok_or("expected the maximum amount of solutions after --max-solutions")?
+                );
+            }
             "--dump" | "-d" => {
-                let mut provided_directory = args.next()
-                    .expect("expected a filepath where dump result to.");
+                dump_path = Some(args.next().ok_or("expected a filepath where dump result to")?);
+            }
+            "--dump-format" => {
+                dump_format = Some(
+                    args.next()
+                        .and_then(|name| DumpFormat::from_name(&name))
+                        .ok_or("expected txt, pla, blif or verilog after --dump-format")?
+                );
+            }
+            "--emit" => {
+                emit_format = Some(
+                    args.next()
+                        .and_then(|name| DumpFormat::from_name(&name))
+                        .ok_or("expected txt, pla, blif or verilog after --emit")?
+                );
+            }
+            _ => {}
+        }
+    }
 
-                if ! provided_directory.ends_with(".txt") {
-                    provided_directory.push_str(".txt");
-                }
+    let sources = [filepath.is_some(), minterms.is_some(), expression.is_some()];
+    if sources.iter().filter(|given| **given).count() > 1 {
+        return Err(String::from("provide only one of a table file, --minterms or --expr"))
+    }
+    if quiet && step_by_step {
+        return Err(String::from("--quiet and --step-by-step can not be given together"))
+    }
+    if dont_cares.is_some() && minterms.is_none() {
+        return Err(String::from("--dont-cares only goes along with --minterms"))
+    }
+    if variables.is_some() && minterms.is_none() && expression.is_none() {
+        return Err(String::from("--vars only goes along with --minterms or --expr"))
+    }
 
-                dump_directory = Some(provided_directory.clone());
+    let table = match (&filepath, minterms, expression) {
+        (Some(path), _, _) => reading.read_table(path),
+        (_, _, Some(formula)) => table_from_expressions(&formula, variables),
+        // the whole Σm notation already tells the don't-cares and the variables.
+        (_, Some(notation), _) if notation.contains('(') => {
+            if dont_cares.is_some() || variables.is_some() {
+                return Err(String::from(
+                    "--dont-cares and --vars can not be given along with the Σm notation"
+                ))
             }
-            _ => {}
+            parse_minterm_notation(&notation)
+        }
+        (_, Some(list), _) => {
+            let minterms = parse_numbers(&list).map_err(|error| error.to_string())?;
+            let dont_cares = dont_cares.unwrap_or_default();
+            table_from_lists(variables, String::from("F"), &minterms, &dont_cares)
+        }
+        (None, None, None) => return Err(String::from(
+            "please, provide a filepath to a truth table (.csv, .pla or .blif), its minterms \
+            with --minterms or its formula with --expr. Consider consulting the help section \
+            with quine-mccluskey --help"
+        ))
+    }.map_err(|error| error.to_string())?;
+    // printing every stage takes far longer than the minimization itself on big tables, so
+    // quiet runs only print the result.
+    let result = if quiet {
+        let result = minimize_with(&table, &options).map_err(|error| error.to_string())?;
+        println!("{}", result.expression());
+        for (index, solution) in result.minimum_solutions().iter().enumerate() {
+            println!("Solution {}: {}", index + 1, solution.expression());
+        }
+        result
+    } else {
+        algorithm(&table, &options, step_by_step).map_err(|error| error.to_string())?
+    };
+
+    // BLIF models and Verilog modules are named after the table they come from.
+    let module = filepath.as_deref().map_or_else(|| String::from("minimized"), model_name);
+
+    if let Some(format) = emit_format {
+        println!("\nThe result in the {} format:", format.name());
+        println!("{}", format.render(&result, &table.variables_names(), &module).trim_end());
+    }
+
+    if let Some(mut path) = dump_path {
+        // the format is told by the extension of the path, which is added when missing.
+        let format = dump_format.unwrap_or_else(|| DumpFormat::from_path(&path));
+        if ! path.ends_with(format.extension()) {
+            path.push_str(format.extension());
+        }
+
+        let content = format.render(&result, &table.variables_names(), &module);
+        fs::write(&path, content)
+            .map_err(|error| QmError::io(&path, error).to_string())?;
+        println!("\nThis result was dumped into file '{}'.", path);
+    }
+
+    Ok(())
+}
+
+// checks a formula against a table file, listing the rows where they disagree.
+fn run_verify(mut args: impl Iterator<Item = String>) -> Result<(), String> {
+    let usage = "usage: quine-mccluskey verify <table file> <formula> [ --outputs <n> ] \
+    [ --input-format <csv | pla | blif> ] [ --fill <zero | one | dont-care> ]";
+    let filepath = args.next().ok_or(usage)?;
+    let formula = args.next().ok_or(usage)?;
+
+    let mut reading = ReadingOptions::default();
+    while let Some(arg) = args.next() {
+        if ! reading.parse(&arg, &mut args)? {
+            return Err(format!("unknown option {arg}. {usage}"))
         }
     }
 
-    let table = TruthTable::from_csv(filepath);
-    let result = algorithm(table, step_by_step);
+    let table = reading.read_table(&filepath).map_err(|error| error.to_string())?;
+    let counterexamples = verify_formula(&table, &formula).map_err(|error| error.to_string())?;
+    if counterexamples.is_empty() {
+        println!("The formula agrees with the table on every row.");
+        return Ok(())
+    }
+
+    println!("The formula disagrees with the table in these rows:");
+    for counterexample in counterexamples.iter().take(MAX_COUNTEREXAMPLES) {
+        println!("{}", counterexample.describe(&table));
+    }
+    if counterexamples.len() > MAX_COUNTEREXAMPLES {
+        println!("and {} more.", counterexamples.len() - MAX_COUNTEREXAMPLES);
+    }
+    Err(format!("disagreements found: {}", counterexamples.len()))
+}
+
+// at most this many disagreements are listed by verify.
+const MAX_COUNTEREXAMPLES: usize = 20;
 
-    match dump_directory {
-        Some(dir) => {
-            fs::write(&dir, result).expect("Error trying to write dump file.");
-            println!("\nThis result was dumped into file '{}'.", dir);
-        },
-        None => {}
+// options telling how a table file is read, shared by every command.
+#[derive(Default)]
+struct ReadingOptions {
+    amount_of_outputs: Option<usize>,
+    input_format: Option<InputFormat>,
+    missing_rows: Option<OutputValue>
+}
+
+impl ReadingOptions {
+    // takes the argument, and its value from args, when it is one of these options.
+    fn parse(&mut self, arg: &str, args: &mut impl Iterator<Item = String>)
+        -> Result<bool, String>
+    {
+        match arg {
+            "--outputs" | "-o" => {
+                self.amount_of_outputs = Some(
+                    args.next()
+                        .and_then(|amount| amount.parse().ok())
+                        .ok_or("expected the amount of output columns after --outputs")?
+                );
+            }
+            "--input-format" | "-i" => {
+                self.input_format = Some(
+                    args.next()
+                        .and_then(|name| InputFormat::from_name(&name))
+                        .ok_or("expected csv, pla or blif after --input-format")?
+                );
+            }
+            "--fill" => {
+                self.missing_rows = match args.next().as_deref() {
+                    Some("zero") => Some(OutputValue::False),
+                    Some("one") => Some(OutputValue::True),
+                    Some("dont-care") => Some(OutputValue::DontCare),
+                    _ => return Err(String::from("expected zero, one or dont-care after --fill"))
+                };
+            }
+            _ => return Ok(false)
+        }
+
+        Ok(true)
+    }
+
+    fn read_table(&self, path: &str) -> quine_mccluskey::Result<TruthTable> {
+        let format = self.input_format.unwrap_or_else(|| InputFormat::from_path(path));
+        TruthTable::from_file(path, format, self.amount_of_outputs.unwrap_or(1), self.missing_rows)
     }
 }
 
 fn print_help() {
-    println!("\nThe first argument must be the filepath to the truth table you want to optimize.");
+    println!("\nThe first argument should be the filepath to the truth table to optimize.");
     println!("This table needs to be in .csv format, filled with 0s and 1s or Vs and Fs.");
-    println!(
-        "Please, make sure it is written from the least significant input to the most significant."
-    );
+    println!("Outputs that don't matter may be marked with x, -, d or *.");
+    println!("Inputs marked with - stand for both values, so a row may give several rows at once.");
+    println!("Rows may come in any order, but each combination of inputs may be given only once.");
+    println!("Only rows with - inputs may overlap, and they must agree on the outputs they share.");
+    println!("Files ending in .pla or .blif are read in the Berkeley PLA or BLIF formats instead.");
+    println!("Without a file, the function may be given by the numbers of its minterms or by a \
+    formula.");
 
-    println!("filepath");
+    println!("[ filepath ]");
     println!("[ --step-by-step ] will run the program pausing after completing every step. \
     User will be prompted to press any key to continue.");
-    println!("[ --dump | -d <path> ] writes the result in the provided file location. .txt \
-    only. It is not necessary provide the .txt extension when specifying the path.");
+    println!("[ --quiet | -q ] prints only the result instead of every stage of the algorithm. \
+    Much faster on big tables, whose coverage maps take long to print.");
+    println!("[ --input-format | -i <csv | pla | blif> ] format of the table, when its \
+    extension does not tell it.");
+    println!("[ --fill <zero | one | dont-care> ] value of the outputs in the rows left out of \
+    a csv table. Without it, every combination of inputs must be given.");
+    println!("[ --minterms | -m <list> ] gives the function by its minterms, such as 0,2,5,7,8, \
+    instead of a table file. Also takes the whole notation, such as \
+    \"f(A,B,C,D) = Σm(0,2,5,7,8) + d(10,11)\".");
+    println!("[ --dont-cares <list> ] minterms whose value does not matter, such as 10,11.");
+    println!("[ --expr <formula> ] gives the function by a formula, such as \
+    \"A'B + AB'C + (A^C)D\", instead of a table file. Takes ! or ' for not, & or * for and, \
+    | or + for or, ^ for exclusive or, parentheses and the constants 0 and 1, and operands \
+    written side by side are multiplied. Several functions may be given separated by ;, each one \
+    named as in F = AB + C. The expressions printed by this tool can be read back.");
+    println!("[ --vars <names> ] names of the variables, such as A,B,C,D. With --minterms, \
+    defaults to as many letters as needed to fit the largest minterm. With --expr, defaults to \
+    the variables found in the formula, each one a letter possibly followed by digits.");
+    println!("[ --outputs | -o <n> ] takes the last n columns of a csv table as outputs. Each \
+    output gets its own expression, and product terms are shared among them. Defaults to 1.");
+    println!("[ --form | -f <sop | pos | best> ] shape of the resulting expression: a sum of \
+    products, a product of sums, or whichever of them has less literals. Defaults to sop.");
+    println!("[ --engine | -e <qm | espresso | auto> ] qm finds every prime and chooses among \
+    them, while espresso is a heuristic that handles much bigger tables but may miss the minimal \
+    expression. auto uses espresso for tables with more than {} variables. Defaults to auto.",
+        Options::default().espresso_threshold);
+    println!("[ --espresso-threshold <n> ] amount of variables above which auto uses espresso.");
+    println!("[ --cover | -c <{}> ] how primes are chosen after the essential ones. \
+    greedy and max-coverage are fast, while branch-and-bound and petrick always find a \
+    minimal cover. Every strategy follows the costs: the greedy ones prefer the cheapest of the \
+    primes covering as many minterms, and the exact ones find the cheapest cover. Defaults to \
+    greedy.", strategies_names().join(" | "));
+    println!("[ --cost <literals | terms | gate-inputs> ] what the cost of the expression \
+    measures. Guides the cover selection and the choice of the best form. Defaults to literals.");
+    println!("[ --weight | -w <variable>=<n> ] makes every literal of the variable cost n, up \
+    to {MAX_WEIGHT}. May be repeated for several variables.");
+    println!("[ --complemented-weight <n> ] multiplies the cost of complemented literals by n, up \
+    to {MAX_WEIGHT}.");
+    println!("[ --all-solutions | -a ] also lists every cover of minimum cost.");
+    println!("[ --max-solutions <n> ] lists at most n covers of minimum cost. Implies \
+    --all-solutions.");
+    println!("[ --dump | -d <path> ] writes the result in the provided file location. Paths \
+    ending in .pla or .blif get the selected cover in the PLA or BLIF formats, paths ending in .v \
+    get a Verilog module computing it, and any other gets the expression as text, adding the \
+    .txt extension when it is missing.");
+    println!("[ --dump-format <txt | pla | blif | verilog> ] format of the dumped result, when its \
+    extension does not tell it.");
+    println!("[ --emit <txt | pla | blif | verilog> ] also prints the result in the given format. \
+    verilog gives a module with an input for each variable and an output for each function.");
     println!("[ -h | --help ] shows this message.");
 
+    println!("\nquine-mccluskey verify <filepath> <formula> checks a formula, such as \
+    \"!AB + AC\", against a table file instead, listing the rows where they disagree. For tables \
+    with several outputs, a formula is given for each one, as in \"F0 = AB; F1 = !C\". Takes \
+    --outputs, --input-format and --fill as above.");
+
     process::exit(0);
 }
+
+// the formats the result may be dumped in: the expression as text, its cover as a PLA, or a
+// BLIF model or Verilog module computing it.
+#[derive(Copy, Clone)]
+enum DumpFormat { Text, Pla, Blif, Verilog }
+
+impl DumpFormat {
+    fn from_name(name: &str) -> Option<Self> {
+        [DumpFormat::Text, DumpFormat::Pla, DumpFormat::Blif, DumpFormat::Verilog].into_iter()
+            .find(|format| format.name() == name)
+    }
+
+    fn from_path(path: &str) -> Self {
+        let path = path.to_lowercase();
+        [DumpFormat::Pla, DumpFormat::Blif, DumpFormat::Verilog].into_iter()
+            .find(|format| path.ends_with(format.extension()))
+            .unwrap_or(DumpFormat::Text)
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            DumpFormat::Text => "txt",
+            DumpFormat::Pla => "pla",
+            DumpFormat::Blif => "blif",
+            DumpFormat::Verilog => "verilog"
+        }
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            DumpFormat::Text => ".txt",
+            DumpFormat::Pla => ".pla",
+            DumpFormat::Blif => ".blif",
+            DumpFormat::Verilog => ".v"
+        }
+    }
+
+    // the result written in this format. module names the BLIF model or the Verilog module.
+    fn render(&self, result: &Minimization, variables: &[String], module: &str) -> String {
+        match self {
+            DumpFormat::Text => result.expression(),
+            DumpFormat::Pla => write_pla(result, variables),
+            DumpFormat::Blif => write_blif(result, variables, module),
+            DumpFormat::Verilog => write_verilog(result, variables, module)
+        }
+    }
+}
+
+fn model_name(filepath: &str) -> String {
+    Path::new(filepath).file_stem()
+        .map(|stem| stem.to_string_lossy().replace(char::is_whitespace, "_"))
+        .unwrap_or_else(|| String::from("minimized"))
+}
+
+fn strategies_names() -> Vec<&'static str> {
+    available_strategies().iter().map(|strategy| strategy.name()).collect()
+}
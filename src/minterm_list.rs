@@ -0,0 +1,192 @@
+use crate::error::{QmError, Result};
+use crate::table_parser::default_variables_names;
+use crate::truth_table::TruthTable;
+
+// reads a function given by the numbers of its minterms, such as
+//     f(A,B,C,D) = Σm(0,2,5,7,8) + d(10,11)
+// the name of the function and its variables may be left out, as in Σm(1,2) + d(3). without
+// variables, they are named A, B and so on, as many as needed to fit the largest number. the sum
+// sign may also be written as ∑ or sum, or be left out, and don't-cares may be written as dc(...).
+pub fn parse_minterm_notation(text: &str) -> Result<TruthTable> {
+    let mut scanner = Scanner::new(text);
+
+    let mut name = String::from("F");
+    let mut variables: Option<Vec<String>> = None;
+    if text.contains('=') {
+        name = scanner.expect_word("the name of the function")?;
+        if scanner.eat('(') {
+            let mut names = vec![scanner.expect_word("a variable")?];
+            while scanner.eat(',') {
+                names.push(scanner.expect_word("a variable")?);
+            }
+            scanner.expect(')')?;
+            variables = Some(names);
+        }
+        scanner.expect('=')?;
+    }
+
+    let mut minterms: Option<Vec<usize>> = None;
+    let mut dont_cares: Vec<usize> = Vec::new();
+    loop {
+        let position = scanner.position();
+        let _ = scanner.eat('Σ') || scanner.eat('∑') || scanner.eat_word("sum");
+        match scanner.expect_word("m or d")?.as_str() {
+            "m" if minterms.is_none() => minterms = Some(scanner.expect_numbers()?),
+            "d" | "dc" => dont_cares.extend(scanner.expect_numbers()?),
+            "m" => return Err(QmError::Notation {
+                position, message: String::from("the minterms are given twice")
+            }),
+            word => return Err(QmError::Notation {
+                position, message: format!("expected m or d, found '{word}'")
+            })
+        }
+
+        if scanner.at_end() { break }
+        scanner.expect('+')?;
+    }
+
+    let minterms = minterms.ok_or_else(|| QmError::Notation {
+        position: 1, message: String::from("the minterms, written as m(...), are missing")
+    })?;
+    table_from_lists(variables, name, &minterms, &dont_cares)
+}
+
+// builds the table of a function given by lists of numbers. without the names of its variables,
+// they are named A, B and so on, as many as needed to fit the largest number.
+pub fn table_from_lists(
+    variables: Option<Vec<String>>,
+    output_name: String,
+    minterms: &[usize],
+    dont_cares: &[usize]
+) -> Result<TruthTable> {
+    let variables = variables.unwrap_or_else(|| {
+        let largest = minterms.iter().chain(dont_cares).copied().max().unwrap_or(0);
+        let amount = (usize::BITS - largest.leading_zeros()).max(1) as usize;
+        default_variables_names(amount)
+    });
+    TruthTable::from_minterms(variables, output_name, minterms, dont_cares)
+}
+
+// reads a list of numbers separated by commas or spaces, such as 0,2,5,7.
+pub fn parse_numbers(text: &str) -> Result<Vec<usize>> {
+    let mut scanner = Scanner::new(text);
+    let numbers = scanner.numbers()?;
+    if ! scanner.at_end() {
+        return Err(QmError::Notation {
+            position: scanner.position(), message: String::from("expected a number")
+        });
+    }
+    Ok(numbers)
+}
+
+// reads a list of names separated by commas or spaces, such as A,B,C.
+pub fn parse_names(text: &str) -> Vec<String> {
+    text.split(|symbol: char| symbol == ',' || symbol.is_whitespace())
+        .filter(|name| ! name.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+// walks the characters of a line, skipping the spaces between its tokens.
+struct Scanner {
+    symbols: Vec<char>,
+    next: usize
+}
+
+impl Scanner {
+    fn new(text: &str) -> Self {
+        Scanner { symbols: text.chars().collect(), next: 0 }
+    }
+
+    // position of the next token, counting from 1.
+    fn position(&mut self) -> usize {
+        self.skip_spaces();
+        self.next + 1
+    }
+
+    fn at_end(&mut self) -> bool {
+        self.skip_spaces();
+        self.next == self.symbols.len()
+    }
+
+    fn skip_spaces(&mut self) {
+        while self.symbols.get(self.next).is_some_and(|symbol| symbol.is_whitespace()) {
+            self.next += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_spaces();
+        self.symbols.get(self.next).copied()
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        let found = self.peek() == Some(expected);
+        if found { self.next += 1 }
+        found
+    }
+
+    fn expect(&mut self, expected: char) -> Result<()> {
+        if self.eat(expected) { return Ok(()) }
+
+        let found = match self.peek() {
+            Some(symbol) => format!("'{symbol}'"),
+            None => String::from("the end")
+        };
+        Err(QmError::Notation {
+            position: self.position(), message: format!("expected '{expected}', found {found}")
+        })
+    }
+
+    fn eat_word(&mut self, expected: &str) -> bool {
+        let start = self.next;
+        let found = self.word().as_deref() == Some(expected);
+        if ! found { self.next = start }
+        found
+    }
+
+    fn word(&mut self) -> Option<String> {
+        self.skip_spaces();
+        let start = self.next;
+        while self.symbols.get(self.next)
+            .is_some_and(|symbol| symbol.is_alphanumeric() || *symbol == '_') {
+            self.next += 1;
+        }
+        (self.next > start).then(|| self.symbols[start..self.next].iter().collect())
+    }
+
+    fn expect_word(&mut self, description: &str) -> Result<String> {
+        let position = self.position();
+        self.word().ok_or_else(|| QmError::Notation {
+            position, message: format!("expected {description}")
+        })
+    }
+
+    // a list of numbers between parentheses.
+    fn expect_numbers(&mut self) -> Result<Vec<usize>> {
+        self.expect('(')?;
+        let numbers = self.numbers()?;
+        self.expect(')')?;
+        Ok(numbers)
+    }
+
+    // numbers separated by commas or spaces, possibly none.
+    fn numbers(&mut self) -> Result<Vec<usize>> {
+        let mut numbers = Vec::new();
+
+        while self.peek().is_some_and(|symbol| symbol.is_ascii_digit()) {
+            let position = self.position();
+            let start = self.next;
+            while self.symbols.get(self.next).is_some_and(|symbol| symbol.is_ascii_digit()) {
+                self.next += 1;
+            }
+            let digits: String = self.symbols[start..self.next].iter().collect();
+            numbers.push(digits.parse().map_err(|_| QmError::Notation {
+                position, message: format!("{digits} is too large to be a minterm")
+            })?);
+            self.eat(',');
+        }
+
+        Ok(numbers)
+    }
+}
@@ -0,0 +1,271 @@
+use std::fs;
+use crate::error::{QmError, Result};
+use crate::table_parser::{
+    all_input_rows, check_amount_of_inputs, check_amount_of_outputs, default_output_names,
+    default_variables_names, ParsedTable
+};
+use crate::truth_table::OutputValue;
+use crate::algorithm::{Form, Minimization};
+
+// what the rows of a PLA file describe, as given by its .type directive.
+// - F: the ones of each output. everything else is a zero;
+// - FD: the ones and the don't-cares. everything else is a zero;
+// - FR: the ones and the zeros. everything else is a don't-care;
+// - FDR: the ones, the don't-cares and the zeros. anything left is a don't-care too.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum PlaType { F, Fd, Fr, Fdr }
+
+impl PlaType {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "f" => Some(PlaType::F),
+            "fd" => Some(PlaType::Fd),
+            "fr" => Some(PlaType::Fr),
+            "fdr" => Some(PlaType::Fdr),
+            _ => None
+        }
+    }
+
+    // value of the rows that no cube mentions.
+    fn unspecified_value(&self) -> OutputValue {
+        match self {
+            PlaType::F | PlaType::Fd => OutputValue::False,
+            PlaType::Fr | PlaType::Fdr => OutputValue::DontCare
+        }
+    }
+
+    // the meaning of an output character of a cube, or None when it says nothing about it.
+    fn output_value(&self, symbol: char) -> Option<Option<OutputValue>> {
+        let describes_zeros = matches!(self, PlaType::Fr | PlaType::Fdr);
+        let describes_dont_cares = matches!(self, PlaType::Fd | PlaType::Fdr);
+
+        match symbol {
+            '1' | '4' => Some(Some(OutputValue::True)),
+            '0' => Some(describes_zeros.then_some(OutputValue::False)),
+            '-' | '2' => Some(describes_dont_cares.then_some(OutputValue::DontCare)),
+            '~' => Some(None),
+            _ => None
+        }
+    }
+}
+
+// reads a table in the Berkeley PLA format used by Espresso. its rows are cubes, where a - in
+// the inputs stands for both values, so each row may describe many rows of the table. outputs
+// marked with a 0 by the .phase directive are complemented after the cubes are read.
+pub fn read_pla(path: &str) -> Result<ParsedTable> {
+    let content = fs::read_to_string(path).map_err(|error| QmError::io(path, error))?;
+
+    let mut amount_of_inputs: Option<usize> = None;
+    let mut amount_of_outputs: Option<usize> = None;
+    let mut variables: Option<Vec<String>> = None;
+    let mut output_names: Option<Vec<String>> = None;
+    let mut declared_cubes: Option<(usize, usize)> = None;
+    let mut pla_type = PlaType::Fd;
+    // outputs whose cubes describe their complement, as marked by a 0 in the .phase directive.
+    let mut phase: Option<(usize, String)> = None;
+    // cubes are kept with their line, so conflicts can point to where they came from.
+    let mut cubes: Vec<(usize, String, String)> = Vec::new();
+
+    for (index, raw_line) in content.lines().enumerate() {
+        let line_number = index + 1;
+        let syntax_error = |message: String| QmError::Syntax { line: line_number, message };
+        let line = raw_line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() { continue }
+
+        if line.starts_with('.') {
+            let mut words = line.split_whitespace();
+            let directive = words.next().unwrap_or("");
+            let arguments: Vec<String> = words.map(String::from).collect();
+            let amount_argument = || -> Result<usize> {
+                match arguments.as_slice() {
+                    [amount] => amount.parse().map_err(|_| syntax_error(
+                        format!("expected an amount after {directive}, found '{amount}'")
+                    )),
+                    _ => Err(syntax_error(format!("expected a single amount after {directive}")))
+                }
+            };
+
+            match directive {
+                ".i" => {
+                    let amount = amount_argument()?;
+                    check_amount_of_inputs(amount)?;
+                    amount_of_inputs = Some(amount);
+                }
+                ".o" => {
+                    let amount = amount_argument()?;
+                    check_amount_of_outputs(amount)?;
+                    amount_of_outputs = Some(amount);
+                }
+                ".ilb" => variables = Some(arguments.clone()),
+                ".ob" => output_names = Some(arguments.clone()),
+                ".p" => declared_cubes = Some((line_number, amount_argument()?)),
+                ".type" => {
+                    pla_type = arguments.first()
+                        .and_then(|name| PlaType::from_name(name))
+                        .filter(|_| arguments.len() == 1)
+                        .ok_or_else(|| syntax_error(
+                            String::from("expected f, fd, fr or fdr after .type")
+                        ))?;
+                }
+                ".phase" => {
+                    let [bits] = arguments.as_slice() else {
+                        return Err(syntax_error(String::from("expected 0s and 1s after .phase")))
+                    };
+                    phase = Some((line_number, bits.clone()));
+                }
+                ".e" | ".end" => break,
+                _ => return Err(syntax_error(format!("unsupported directive {directive}")))
+            }
+            continue
+        }
+
+        let (inputs_amount, outputs_amount) = match (amount_of_inputs, amount_of_outputs) {
+            (Some(inputs), Some(outputs)) => (inputs, outputs),
+            _ => return Err(syntax_error(String::from(".i and .o must come before the cubes")))
+        };
+
+        // spaces and bars only make the cubes easier to read.
+        let symbols: Vec<char> = line.chars()
+            .filter(|symbol| ! symbol.is_whitespace() && *symbol != '|')
+            .collect();
+        if symbols.len() != inputs_amount + outputs_amount {
+            return Err(syntax_error(format!(
+                "expected {inputs_amount} input and {outputs_amount} output values, but found \
+                '{line}'"
+            )));
+        }
+
+        let (cube_inputs, cube_outputs) = symbols.split_at(inputs_amount);
+        cubes.push((
+            line_number, cube_inputs.iter().collect(), cube_outputs.iter().collect()
+        ));
+    }
+
+    let amount_of_inputs = amount_of_inputs
+        .ok_or_else(|| QmError::InvalidHeader(String::from("the .i directive is missing")))?;
+    let amount_of_outputs = amount_of_outputs
+        .ok_or_else(|| QmError::InvalidHeader(String::from("the .o directive is missing")))?;
+    let variables = variables.unwrap_or_else(|| default_variables_names(amount_of_inputs));
+    let output_names = output_names.unwrap_or_else(|| default_output_names(amount_of_outputs));
+
+    if variables.len() != amount_of_inputs || output_names.len() != amount_of_outputs {
+        return Err(QmError::InvalidHeader(format!(
+            ".ilb and .ob must name the {amount_of_inputs} inputs and {amount_of_outputs} \
+            outputs declared by .i and .o"
+        )));
+    }
+    let complemented: Vec<bool> = match phase {
+        None => vec![false; amount_of_outputs],
+        Some((line, bits)) => {
+            if bits.len() != amount_of_outputs || bits.chars().any(|bit| ! "01".contains(bit)) {
+                return Err(QmError::Syntax { line, message: format!(
+                    ".phase must have a 0 or 1 for each of the {amount_of_outputs} outputs"
+                ) });
+            }
+            bits.chars().map(|bit| bit == '0').collect()
+        }
+    };
+    if let Some((line, amount)) = declared_cubes {
+        if amount != cubes.len() {
+            return Err(QmError::Syntax { line, message: format!(
+                ".p declares {amount} cubes, but {} were found", cubes.len()
+            ) });
+        }
+    }
+
+    let inputs = all_input_rows(amount_of_inputs)?;
+    let mut outputs = vec![vec![None; amount_of_outputs]; inputs.len()];
+
+    for (line, cube_inputs, cube_outputs) in &cubes {
+        let mut values: Vec<Option<OutputValue>> = Vec::new();
+        for (column, symbol) in cube_outputs.chars().enumerate() {
+            let value = pla_type.output_value(symbol).ok_or_else(|| QmError::Syntax {
+                line: *line,
+                message: format!("invalid value '{symbol}' for output {}", output_names[column])
+            })?;
+            values.push(value);
+        }
+
+        for minterm in cube_minterms(cube_inputs, *line)? {
+            for (output, value) in values.iter().enumerate() {
+                let Some(value) = value else { continue };
+                let entry = &mut outputs[minterm][output];
+
+                match *entry {
+                    // a don't-care given for some row wins over its other values.
+                    Some((_, OutputValue::DontCare)) => {},
+                    Some((previous_line, previous))
+                        if previous != *value && *value != OutputValue::DontCare => {
+                        return Err(QmError::Syntax { line: *line, message: format!(
+                            "output {} of minterm {minterm} is {}, but line {previous_line} \
+                            made it {}", output_names[output], value.symbol(), previous.symbol()
+                        ) });
+                    }
+                    _ => *entry = Some((*line, *value))
+                }
+            }
+        }
+    }
+
+    let outputs = outputs.into_iter()
+        .map(|row| {
+            row.into_iter()
+                .zip(complemented.iter())
+                .map(|(value, complemented)| {
+                    let value = value.map_or(pla_type.unspecified_value(), |(_, value)| value);
+                    if *complemented { value.complement() } else { value }
+                })
+                .collect()
+        })
+        .collect();
+
+    Ok((variables, output_names, inputs, outputs))
+}
+
+// writes a minimized cover in the PLA format, one cube for each of its terms. a product of sums
+// is written as the cover of the complement of each output, which is marked by the .phase
+// directive, so that reading the file back gives the same function.
+pub fn write_pla(minimization: &Minimization, variables: &[String]) -> String {
+    let output_names = minimization.output_names();
+    let mut pla = format!(
+        ".i {}\n.o {}\n.ilb {}\n.ob {}\n.type f\n",
+        variables.len(), output_names.len(), variables.join(" "), output_names.join(" ")
+    );
+    if minimization.form() == Form::ProductOfSums {
+        pla.push_str(&format!(".phase {}\n", "0".repeat(output_names.len())));
+    }
+    pla.push_str(&format!(".p {}\n", minimization.cover().len()));
+
+    for term in minimization.cover() {
+        let outputs: String = (0..output_names.len())
+            .map(|output| if minimization.output_cover(output).contains(term) { '1' } else { '0' })
+            .collect();
+        pla.push_str(&format!("{} {outputs}\n", term.get_binary_representation()));
+    }
+
+    pla.push_str(".e\n");
+    pla
+}
+
+// the numbers of the minterms inside a cube such as 1-0, the first input being the most
+// significant one.
+fn cube_minterms(cube: &str, line: usize) -> Result<Vec<usize>> {
+    let mut minterms: Vec<usize> = vec![0];
+
+    for (column, symbol) in cube.chars().enumerate() {
+        let bits: &[usize] = match symbol {
+            '0' => &[0],
+            '1' => &[1],
+            '-' | '~' => &[0, 1],
+            _ => return Err(QmError::Syntax {
+                line, message: format!("invalid value '{symbol}' for input {}", column + 1)
+            })
+        };
+
+        minterms = minterms.iter()
+            .flat_map(|minterm| bits.iter().map(move |bit| minterm << 1 | bit))
+            .collect();
+    }
+
+    Ok(minterms)
+}
@@ -1,64 +1,260 @@
-use csv::Reader;
+use csv::ReaderBuilder;
+use crate::error::{QmError, Result};
+use crate::truth_table::{OutputValue, MAX_OUTPUTS};
 
-pub fn read_csv(path: &str) -> (Vec<String>, Vec<Vec<bool>>, Vec<bool>) {
-    let mut reader = Reader::from_path(path).expect("Error getting reader");
+// input names, output names, input rows and output values of each row, in this order.
+pub type ParsedTable = (Vec<String>, Vec<String>, Vec<Vec<bool>>, Vec<Vec<OutputValue>>);
 
-    let headers: Vec<String> = reader.headers().expect("Error getting headers")
-        .iter().map(|s| String::from(s)).collect();
+// the same, for tables whose input cells may hold a - that stands for both values. such rows are
+// cubes, describing every row that matches their other cells.
+pub type ParsedCubes = (Vec<String>, Vec<String>, Vec<Vec<Option<bool>>>, Vec<Vec<OutputValue>>);
 
-    let values: Vec<Vec<bool>> = reader.records()
-        .map(
-            |row| row.expect("Error while extracting row")
-                .iter()
-                .map(turn_input_into_boolean)
-                .collect()
-        ).collect();
+// input rows and output values of each row.
+pub type TableRows = (Vec<Vec<bool>>, Vec<Vec<OutputValue>>);
 
-    let inputs: Vec<Vec<bool>> = values.iter().map(
-        |row| row[..row.len() - 1].to_vec()
-    ).collect();
+// reads a csv truth table. the last amount_of_outputs columns are taken as outputs, all the
+// others as inputs. input cells holding a - make the row stand for both values of that input.
+pub fn read_csv(path: &str, amount_of_outputs: usize) -> Result<ParsedCubes> {
+    // rows of unexpected width are reported by us, with their position.
+    let mut reader = ReaderBuilder::new().flexible(true).from_path(path)
+        .map_err(|error| QmError::io(path, error.into()))?;
 
-    let output = values.iter().map(
-        |row| *row.last().expect("Error while extracting output")
-    ).collect();
+    let mut headers: Vec<String> = reader.headers()
+        .map_err(|error| QmError::io(path, error.into()))?
+        .iter().map(String::from).collect();
 
-    assert_ascending_order(&inputs);
+    if amount_of_outputs == 0 || headers.len() <= amount_of_outputs {
+        return Err(QmError::InvalidHeader(format!(
+            "a table with {amount_of_outputs} output(s) needs more than {amount_of_outputs} \
+            columns, but {} were found", headers.len()
+        )));
+    }
 
-    (headers, inputs, output)
-}
+    // every column is referred to by its name, so each of them needs one, and no two of them
+    // may share it.
+    for (column, name) in headers.iter().enumerate() {
+        if name.trim().is_empty() {
+            return Err(QmError::InvalidHeader(format!("column {} has no name", column + 1)));
+        }
+        if let Some(first_column) = headers[..column].iter().position(|other| other == name) {
+            return Err(QmError::InvalidHeader(format!(
+                "column {} repeats the name '{name}' of column {}", column + 1, first_column + 1
+            )));
+        }
+    }
+
+    let amount_of_inputs = headers.len() - amount_of_outputs;
+    let mut inputs: Vec<Vec<Option<bool>>> = Vec::new();
+    let mut outputs: Vec<Vec<OutputValue>> = Vec::new();
 
-fn turn_input_into_boolean(c: &str) -> bool {
-    if c.len() != 1 {
-        panic!("Truth table cell values must be single character.");
+    for (index, record) in reader.records().enumerate() {
+        let record = record.map_err(|error| QmError::io(path, error.into()))?;
+        let row = index + 1;
+
+        if record.len() != headers.len() {
+            return Err(QmError::WidthMismatch {
+                row, expected: headers.len(), found: record.len()
+            });
+        }
+
+        let values = record.iter()
+            .take(amount_of_inputs)
+            .enumerate()
+            .map(|(column, cell)| match cell.trim() {
+                "-" => Ok(None),
+                _ => turn_input_into_boolean(cell, row, column + 1).map(Some)
+            })
+            .collect::<Result<Vec<Option<bool>>>>()?;
+        let output_values = record.iter()
+            .enumerate()
+            .skip(amount_of_inputs)
+            .map(|(column, cell)| turn_output_into_value(cell, row, column + 1))
+            .collect::<Result<Vec<OutputValue>>>()?;
+
+        inputs.push(values);
+        outputs.push(output_values);
     }
 
+    let output_names = headers.split_off(amount_of_inputs);
+
+    Ok((headers, output_names, inputs, outputs))
+}
+
+fn turn_input_into_boolean(c: &str, row: usize, column: usize) -> Result<bool> {
+    let bad_cell = || QmError::BadCell { row, column, value: c.to_string() };
+
+    if c.len() != 1 { return Err(bad_cell()) }
+
     let lower = c.to_lowercase();
     let comp = lower.as_str();
 
     match comp {
-        "1" | "v" | "t" => true,
-        "0" | "f" => false,
-        other => panic!("Unknown character found: {}.", other)
+        "1" | "v" | "t" => Ok(true),
+        "0" | "f" => Ok(false),
+        _ => Err(bad_cell())
+    }
+}
+
+// output cells may also mark the row as a don't-care.
+fn turn_output_into_value(c: &str, row: usize, column: usize) -> Result<OutputValue> {
+    match c.to_lowercase().as_str() {
+        "x" | "-" | "d" | "*" => Ok(OutputValue::DontCare),
+        _ => match turn_input_into_boolean(c, row, column)? {
+            true => Ok(OutputValue::True),
+            false => Ok(OutputValue::False)
+        }
+    }
+}
+
+// tables keep one row for each combination of their inputs, so past this many inputs they would
+// not fit in memory.
+pub const MAX_DENSE_VARIABLES: usize = 20;
+
+pub fn check_amount_of_inputs(amount_of_variables: usize) -> Result<()> {
+    if amount_of_variables > MAX_DENSE_VARIABLES {
+        return Err(QmError::InvalidHeader(format!(
+            "a table may have at most {MAX_DENSE_VARIABLES} inputs, since it keeps a row for \
+            each combination of them, but {amount_of_variables} were given"
+        )));
+    }
+
+    Ok(())
+}
+
+pub fn check_amount_of_outputs(amount_of_outputs: usize) -> Result<()> {
+    if amount_of_outputs == 0 || amount_of_outputs > MAX_OUTPUTS {
+        return Err(QmError::InvalidHeader(format!(
+            "a table needs between 1 and {MAX_OUTPUTS} outputs, but {amount_of_outputs} were given"
+        )));
     }
+
+    Ok(())
+}
+
+// every combination of inputs, in ascending order.
+pub fn all_input_rows(amount_of_variables: usize) -> Result<Vec<Vec<bool>>> {
+    check_amount_of_inputs(amount_of_variables)?;
+
+    Ok((0..1usize << amount_of_variables)
+        .map(|number| {
+            (0..amount_of_variables)
+                .map(|variable| number & (1 << (amount_of_variables - 1 - variable)) != 0)
+                .collect()
+        })
+        .collect())
+}
+
+// names for tables whose format does not name their columns: A, B, C and so on, or X0, X1 and
+// so on when there are more variables than letters.
+pub fn default_variables_names(amount: usize) -> Vec<String> {
+    if amount <= 26 {
+        return (b'A'..).take(amount).map(|letter| (letter as char).to_string()).collect()
+    }
+
+    (0..amount).map(|variable| format!("X{variable}")).collect()
+}
+
+// F for a single output, or F0, F1 and so on.
+pub fn default_output_names(amount: usize) -> Vec<String> {
+    if amount == 1 { return vec![String::from("F")] }
+
+    (0..amount).map(|output| format!("F{output}")).collect()
 }
 
 pub fn convert_boolean_row_to_number(row: &[bool]) -> usize {
-    let mut weight: u32 = 0;
     let mut sum: usize = 0;
 
-    for value in row.iter().rev() {
-        if *value { sum += 2usize.pow(weight) }
-        weight += 1;
+    for (weight, value) in row.iter().rev().enumerate() {
+        if *value { sum += 2usize.pow(weight as u32) }
     }
 
     sum
 }
 
-// check if inputs where provided in ascending order
-fn assert_ascending_order(inputs: &Vec<Vec<bool>>) {
+// puts rows given in any order in ascending order, expanding the cubes among them into every row
+// they stand for. every input combination may be given only once, except by overlapping cubes
+// that agree on its outputs. the combinations not given take the missing_rows value in all
+// outputs. without it, every combination must be given.
+pub fn arrange_rows(
+    inputs: Vec<Vec<Option<bool>>>,
+    outputs: Vec<Vec<OutputValue>>,
+    amount_of_variables: usize,
+    missing_rows: Option<OutputValue>
+) -> Result<TableRows> {
+    let amount_of_outputs = outputs.first().map_or(0, |row| row.len());
+    // the row that first gave each combination, counting from 1, whether that row is a cube,
+    // and the outputs it gave.
+    let mut arranged: Vec<Option<(usize, bool, Vec<OutputValue>)>> =
+        vec![None; 1usize << amount_of_variables];
+
+    for (index, (cube, values)) in inputs.iter().zip(outputs).enumerate() {
+        let row = index + 1;
+        let is_cube = cube.iter().any(|cell| cell.is_none());
+
+        for minterm in cube_minterms(cube) {
+            match &arranged[minterm] {
+                Some((first_row, first_is_cube, first_values)) => {
+                    let first_row = *first_row;
+                    if *first_values != values {
+                        return Err(QmError::ConflictingRows { row, first_row, minterm });
+                    }
+                    if ! is_cube && ! first_is_cube {
+                        return Err(QmError::DuplicateRow { row, first_row, minterm });
+                    }
+                }
+                None => arranged[minterm] = Some((row, is_cube, values.clone()))
+            }
+        }
+    }
+
+    // cubes may stand for many rows, so the rows found are the combinations given.
+    let given_rows = arranged.iter().filter(|row| row.is_some()).count();
+    let outputs = arranged.into_iter()
+        .map(|row| match (row, missing_rows) {
+            (Some((_, _, values)), _) => Ok(values),
+            (None, Some(value)) => Ok(vec![value; amount_of_outputs]),
+            (None, None) => Err(QmError::MissingRows {
+                expected: 1usize << amount_of_variables, found: given_rows
+            })
+        })
+        .collect::<Result<Vec<Vec<OutputValue>>>>()?;
+
+    Ok((all_input_rows(amount_of_variables)?, outputs))
+}
+
+// the numbers of the input combinations a cube stands for, where None cells take both values.
+fn cube_minterms(cube: &[Option<bool>]) -> Vec<usize> {
+    let mut minterms: Vec<usize> = vec![0];
+
+    for cell in cube {
+        let bits: &[usize] = match cell {
+            Some(false) => &[0],
+            Some(true) => &[1],
+            None => &[0, 1]
+        };
+        minterms = minterms.iter()
+            .flat_map(|minterm| bits.iter().map(move |bit| minterm << 1 | bit))
+            .collect();
+    }
+
+    minterms
+}
+
+// check if inputs where provided in ascending order, with no missing rows.
+pub fn assert_ascending_order(inputs: &[Vec<bool>], amount_of_variables: usize) -> Result<()> {
     for (expected_value, row) in inputs.iter().enumerate() {
-        if expected_value != convert_boolean_row_to_number(row) {
-            panic!("Inputs were not provided in ascending order or there are missing rows");
+        let found = convert_boolean_row_to_number(row);
+        if expected_value != found {
+            return Err(QmError::UnorderedRows {
+                row: expected_value + 1, expected: expected_value, found
+            });
         }
     }
+
+    let expected_rows = 1usize << amount_of_variables;
+    if inputs.len() != expected_rows {
+        return Err(QmError::MissingRows { expected: expected_rows, found: inputs.len() });
+    }
+
+    Ok(())
 }
@@ -1,35 +1,285 @@
-use crate::table_parser::read_csv;
+use std::path::Path;
+use crate::table_parser::{
+    read_csv, all_input_rows, arrange_rows, assert_ascending_order, check_amount_of_inputs,
+    check_amount_of_outputs
+};
+use crate::pla::read_pla;
+use crate::blif::read_blif;
+use crate::error::{QmError, Result};
+
+// the value a function takes for some row. rows marked as DontCare may be treated either as
+// true or false, whichever leads to a simpler expression.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum OutputValue { False, True, DontCare }
+
+impl OutputValue {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            OutputValue::False => "0",
+            OutputValue::True => "1",
+            OutputValue::DontCare => "x"
+        }
+    }
+
+    pub fn complement(&self) -> Self {
+        match self {
+            OutputValue::False => OutputValue::True,
+            OutputValue::True => OutputValue::False,
+            OutputValue::DontCare => OutputValue::DontCare
+        }
+    }
+}
+
+// the formats tables may be read from.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum InputFormat { Csv, Pla, Blif }
+
+impl InputFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "csv" => Some(InputFormat::Csv),
+            "pla" => Some(InputFormat::Pla),
+            "blif" => Some(InputFormat::Blif),
+            _ => None
+        }
+    }
+
+    // .pla and .blif files are read in their formats, and everything else as csv.
+    pub fn from_path(path: &str) -> Self {
+        let extension = Path::new(path).extension().and_then(|extension| extension.to_str());
+        match extension.map(|extension| extension.to_lowercase()).as_deref() {
+            Some("pla") => InputFormat::Pla,
+            Some("blif") => InputFormat::Blif,
+            _ => InputFormat::Csv
+        }
+    }
+}
+
+// implicants record the outputs they belong to in a 64 bits mask.
+pub const MAX_OUTPUTS: usize = 64;
 
 pub struct TruthTable {
     variables: Vec<String>,
+    output_names: Vec<String>,
     inputs: Vec<Vec<bool>>,
-    output: Vec<bool>
+    outputs: Vec<Vec<OutputValue>>
 }
 
 impl TruthTable {
     pub fn input_rows(&self) -> &Vec<Vec<bool>> { &self.inputs }
     pub fn variables_names(&self) -> Vec<String> { self.variables.clone() }
-    pub fn amount_of_variables(&self) -> usize {
-        self.inputs.get(0).unwrap_or(&Vec::new() as &Vec<bool>).len()
+    pub fn output_names(&self) -> &Vec<String> { &self.output_names }
+    pub fn amount_of_variables(&self) -> usize { self.variables.len() }
+    pub fn amount_of_outputs(&self) -> usize { self.output_names.len() }
+    pub fn row_value(&self, index: usize, output: usize) -> OutputValue {
+        self.outputs[index][output]
+    }
+
+    // mask of the outputs that take the given value at some row.
+    pub fn outputs_mask(&self, index: usize, value: OutputValue) -> u64 {
+        self.outputs[index].iter()
+            .enumerate()
+            .filter(|(_, output_value)| **output_value == value)
+            .fold(0, |mask, (output, _)| mask | 1 << output)
+    }
+
+    // builds a table from its rows, which must list every input combination in ascending order.
+    // each row holds one value for each output.
+    pub fn new(
+        variables: Vec<String>,
+        output_names: Vec<String>,
+        inputs: Vec<Vec<bool>>,
+        outputs: Vec<Vec<OutputValue>>
+    ) -> Result<Self> {
+        check_shape(&variables, &output_names, &inputs, &outputs)?;
+        assert_ascending_order(&inputs, variables.len())?;
+
+        Ok(TruthTable { variables, output_names, inputs, outputs })
+    }
+
+    // builds a table from rows given in any order. combinations left out take the missing_rows
+    // value, or are reported as missing when it is not given.
+    pub fn from_rows(
+        variables: Vec<String>,
+        output_names: Vec<String>,
+        inputs: Vec<Vec<bool>>,
+        outputs: Vec<Vec<OutputValue>>,
+        missing_rows: Option<OutputValue>
+    ) -> Result<Self> {
+        let cubes = inputs.into_iter()
+            .map(|row| row.into_iter().map(Some).collect())
+            .collect();
+        TruthTable::from_cubes(variables, output_names, cubes, outputs, missing_rows)
+    }
+
+    // builds a table from rows given in any order, whose None cells stand for both values of
+    // their inputs. combinations left out take the missing_rows value, or are reported as missing
+    // when it is not given.
+    pub fn from_cubes(
+        variables: Vec<String>,
+        output_names: Vec<String>,
+        cubes: Vec<Vec<Option<bool>>>,
+        outputs: Vec<Vec<OutputValue>>,
+        missing_rows: Option<OutputValue>
+    ) -> Result<Self> {
+        check_shape(&variables, &output_names, &cubes, &outputs)?;
+        let (inputs, outputs) = arrange_rows(cubes, outputs, variables.len(), missing_rows)?;
+
+        Ok(TruthTable { variables, output_names, inputs, outputs })
+    }
+
+    // builds the table of a single output from the numbers of its ones and don't-cares, the
+    // first variable being the most significant bit. every other row is a zero.
+    pub fn from_minterms(
+        variables: Vec<String>,
+        output_name: String,
+        minterms: &[usize],
+        dont_cares: &[usize]
+    ) -> Result<Self> {
+        check_shape::<bool>(&variables, std::slice::from_ref(&output_name), &[], &[])?;
+
+        let amount_of_rows = 1usize << variables.len();
+        let mut outputs = vec![vec![OutputValue::False]; amount_of_rows];
+        let given = minterms.iter()
+            .map(|minterm| (*minterm, OutputValue::True))
+            .chain(dont_cares.iter().map(|minterm| (*minterm, OutputValue::DontCare)));
+
+        for (minterm, value) in given {
+            if minterm >= amount_of_rows {
+                return Err(QmError::InvalidMinterm { minterm, reason: format!(
+                    "does not fit in {} variables, whose minterms go up to {}",
+                    variables.len(), amount_of_rows - 1
+                ) });
+            }
+            if outputs[minterm][0] == OutputValue::True && value == OutputValue::DontCare {
+                return Err(QmError::InvalidMinterm {
+                    minterm, reason: String::from("is given both as a one and as a don't-care")
+                });
+            }
+            outputs[minterm][0] = value;
+        }
+
+        let inputs = all_input_rows(variables.len())?;
+        TruthTable::new(variables, vec![output_name], inputs, outputs)
+    }
+
+    // reads a table whose last column is its only output.
+    pub fn from_csv(path: &str) -> Result<Self> {
+        TruthTable::from_csv_with_outputs(path, 1)
+    }
+
+    // reads a table whose last amount_of_outputs columns are outputs. its rows may come in any
+    // order, but all of them must be present.
+    pub fn from_csv_with_outputs(path: &str, amount_of_outputs: usize) -> Result<Self> {
+        TruthTable::from_csv_with(path, amount_of_outputs, None)
+    }
+
+    // reads a table whose last amount_of_outputs columns are outputs, filling the rows it leaves
+    // out with the missing_rows value.
+    pub fn from_csv_with(
+        path: &str,
+        amount_of_outputs: usize,
+        missing_rows: Option<OutputValue>
+    ) -> Result<Self> {
+        let (variables, output_names, inputs, outputs) = read_csv(path, amount_of_outputs)?;
+        TruthTable::from_cubes(variables, output_names, inputs, outputs, missing_rows)
+    }
+
+    // reads a table in the Berkeley PLA format, which declares its own inputs and outputs.
+    pub fn from_pla(path: &str) -> Result<Self> {
+        let (variables, output_names, inputs, outputs) = read_pla(path)?;
+        TruthTable::new(variables, output_names, inputs, outputs)
     }
-    pub fn row_value(&self, index: usize) -> bool {
-        *self.output.get(index).expect("Not a valid row index")
+
+    // reads the table of a single-level BLIF model.
+    pub fn from_blif(path: &str) -> Result<Self> {
+        let (variables, output_names, inputs, outputs) = read_blif(path)?;
+        TruthTable::new(variables, output_names, inputs, outputs)
     }
 
-    pub fn from_csv(path: &str) -> Self {
-        let (variables, inputs, output) = read_csv(path);
-        TruthTable { variables, inputs, output }
+    // reads a table in the given format. amount_of_outputs and missing_rows are only needed by
+    // csv tables, since the other formats declare their outputs and tell the value of every row.
+    pub fn from_file(
+        path: &str,
+        format: InputFormat,
+        amount_of_outputs: usize,
+        missing_rows: Option<OutputValue>
+    ) -> Result<Self> {
+        match format {
+            InputFormat::Csv => TruthTable::from_csv_with(path, amount_of_outputs, missing_rows),
+            InputFormat::Pla => TruthTable::from_pla(path),
+            InputFormat::Blif => TruthTable::from_blif(path)
+        }
+    }
+
+    // the table of the negated function. don't-cares stay as they are.
+    pub fn complement(&self) -> TruthTable {
+        TruthTable {
+            variables: self.variables.clone(),
+            output_names: self.output_names.clone(),
+            inputs: self.inputs.clone(),
+            outputs: self.outputs.iter()
+                .map(|row| row.iter().map(|value| value.complement()).collect())
+                .collect()
+        }
     }
 
     pub fn print_table(&self) {
         self.variables.iter().for_each(|h| print!("{h}\t"));
-        println!();
+        println!("{}", self.output_names.join("\t"));
         self.inputs.iter().enumerate().for_each(
             |(index, row)| {
                 row.iter().for_each(|cell| print!("{} \t", *cell as i8));
-                print!("{}", self.output[index] as i8);
-                println!();
+                let values: Vec<&str> = self.outputs[index].iter()
+                    .map(|value| value.symbol())
+                    .collect();
+                println!("{}", values.join(" \t"));
             }
         );
     }
-}
\ No newline at end of file
+}
+
+// checks that the table has a usable amount of inputs and outputs, and that every row has a value
+// for each one of them.
+fn check_shape<T>(
+    variables: &[String],
+    output_names: &[String],
+    inputs: &[Vec<T>],
+    outputs: &[Vec<OutputValue>]
+) -> Result<()> {
+    if variables.is_empty() {
+        return Err(QmError::InvalidHeader(String::from("a table needs at least one input")));
+    }
+
+    check_amount_of_inputs(variables.len())?;
+
+    if variables.iter().chain(output_names).any(|name| name.trim().is_empty()) {
+        return Err(QmError::InvalidHeader(String::from("every input and output needs a name")));
+    }
+
+    check_amount_of_outputs(output_names.len())?;
+
+    for (index, row) in inputs.iter().enumerate() {
+        if row.len() != variables.len() {
+            return Err(QmError::WidthMismatch {
+                row: index + 1, expected: variables.len(), found: row.len()
+            });
+        }
+    }
+
+    if outputs.len() != inputs.len() {
+        return Err(QmError::MissingRows { expected: inputs.len(), found: outputs.len() });
+    }
+
+    for (index, row) in outputs.iter().enumerate() {
+        if row.len() != output_names.len() {
+            return Err(QmError::WidthMismatch {
+                row: index + 1,
+                expected: variables.len() + output_names.len(),
+                found: variables.len() + row.len()
+            });
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use crate::truth_table::{OutputValue, TruthTable};
+use crate::algorithm::{Form, Minimization};
+use crate::expression::{parse_functions, Expression};
+use crate::error::{QmError, Result};
+
+// a row where a formula disagrees with the table, for one of its outputs.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Counterexample {
+    pub row: usize,
+    pub output: usize,
+    pub expected: OutputValue,
+    pub found: bool
+}
+
+impl Counterexample {
+    // such as "row 5 (A=0, B=1, C=0, D=1): F is 1 in the table, but the formula gives 0".
+    pub fn describe(&self, table: &TruthTable) -> String {
+        let inputs: Vec<String> = table.variables_names().iter()
+            .zip(&table.input_rows()[self.row])
+            .map(|(name, value)| format!("{name}={}", *value as u8))
+            .collect();
+        format!(
+            "row {} ({}): {} is {} in the table, but the formula gives {}",
+            self.row, inputs.join(", "), table.output_names()[self.output],
+            self.expected.symbol(), self.found as u8
+        )
+    }
+}
+
+// evaluates the cover of every output of a minimization on each row of the table it came from.
+// a sum of products holds where some of its terms does, and a product of sums fails where some
+// of its terms, which describe zeros, does. rows that are don't-cares may take any value.
+pub fn verify_minimization(table: &TruthTable, minimization: &Minimization)
+    -> Vec<Counterexample>
+{
+    let is_product_of_sums = minimization.form() == Form::ProductOfSums;
+    compare(table, |row, output| {
+        let covered = minimization.output_cover(output).iter()
+            .any(|implicant| implicant.covers_minterm(row as u64));
+        covered != is_product_of_sums
+    })
+}
+
+// evaluates one formula for each output of the table on each of its rows.
+pub fn verify_expressions(table: &TruthTable, expressions: &[Expression]) -> Vec<Counterexample> {
+    compare(table, |row, output| expressions[output].evaluate(&table.input_rows()[row]))
+}
+
+// checks formulas written as text against the table. a table with a single output takes a single
+// formula, while the formulas of the outputs of other tables are named after them, as in
+// F0 = AB; F1 = !C. formulas may use only the variables of the table.
+pub fn verify_formula(table: &TruthTable, text: &str) -> Result<Vec<Counterexample>> {
+    let functions = parse_functions(text, &table.variables_names())?;
+    let output_names = table.output_names();
+    let mut expressions: Vec<Option<Expression>> = vec![None; output_names.len()];
+
+    for function in functions {
+        let output = match &function.name {
+            None if output_names.len() == 1 => 0,
+            None => return Err(QmError::Notation {
+                position: function.position,
+                message: format!(
+                    "the table has outputs {}, so each formula must be named after one of them, \
+                    as in {} = AB + C", output_names.join(", "), output_names[0]
+                )
+            }),
+            Some(name) => output_names.iter()
+                .position(|output_name| output_name == name)
+                .ok_or_else(|| QmError::Notation {
+                    position: function.position,
+                    message: format!(
+                        "{name} is not an output of the table, whose outputs are {}",
+                        output_names.join(", ")
+                    )
+                })?
+        };
+        if expressions[output].is_some() {
+            return Err(QmError::Notation {
+                position: function.position,
+                message: format!("{} is given more than one formula", output_names[output])
+            });
+        }
+        expressions[output] = Some(function.expression);
+    }
+
+    let expressions = expressions.into_iter()
+        .zip(output_names)
+        .map(|(expression, name)| expression.ok_or_else(|| QmError::Notation {
+            position: 1, message: format!("no formula was given for {name}")
+        }))
+        .collect::<Result<Vec<Expression>>>()?;
+
+    Ok(verify_expressions(table, &expressions))
+}
+
+fn compare<F: Fn(usize, usize) -> bool>(table: &TruthTable, value_of: F) -> Vec<Counterexample> {
+    let mut counterexamples = Vec::new();
+
+    for row in 0..table.input_rows().len() {
+        for output in 0..table.amount_of_outputs() {
+            let expected = table.row_value(row, output);
+            if expected == OutputValue::DontCare { continue }
+
+            let found = value_of(row, output);
+            if found != (expected == OutputValue::True) {
+                counterexamples.push(Counterexample { row, output, expected, found });
+            }
+        }
+    }
+
+    counterexamples
+}
@@ -0,0 +1,110 @@
+use crate::algorithm::{Form, Minimization};
+use crate::implicant::Implicant;
+
+// words of Verilog that can not be used as names of ports or modules.
+const KEYWORDS: [&str; 46] = [
+    "always", "and", "assign", "begin", "buf", "case", "casex", "casez", "default", "defparam",
+    "else", "end", "endcase", "endfunction", "endmodule", "endtask", "for", "function", "if",
+    "initial", "inout", "input", "integer", "module", "nand", "negedge", "nor", "not", "or",
+    "output", "parameter", "posedge", "real", "reg", "repeat", "signed", "supply0", "supply1",
+    "task", "time", "tri", "wait", "while", "wire", "xnor", "xor"
+];
+
+// writes the minimized expression as a Verilog module, with an input port for each variable and
+// an output port for each function, named as in the table. each output is computed by an assign
+// statement from its terms. products are written with & and ~, and the terms of a sum of products
+// are joined with |. a product of sums joins its sum terms with & instead.
+pub fn write_verilog(minimization: &Minimization, variables: &[String], module: &str) -> String {
+    let output_names = minimization.output_names();
+    let ports: Vec<String> = variables.iter()
+        .map(|name| format!("    input wire {}", identifier(name)))
+        .chain(output_names.iter().map(|name| format!("    output wire {}", identifier(name))))
+        .collect();
+
+    let mut verilog = format!("module {} (\n{}\n);\n", module_name(module), ports.join(",\n"));
+
+    for (output, name) in output_names.iter().enumerate() {
+        let cover = minimization.output_cover(output);
+        let value = match minimization.form() {
+            Form::ProductOfSums => join_terms(cover, clause, " & ", "1'b1"),
+            _ => join_terms(cover, product, " | ", "1'b0")
+        };
+        verilog.push_str(&format!("    assign {} = {value};\n", identifier(name)));
+    }
+
+    verilog.push_str("endmodule\n");
+    verilog
+}
+
+// an empty cover is the constant given, and a single term needs no parentheses.
+fn join_terms(
+    cover: &[Implicant],
+    term: fn(&Implicant) -> (String, usize),
+    operator: &str,
+    empty: &str
+) -> String {
+    let terms: Vec<(String, usize)> = cover.iter().map(term).collect();
+    match terms.as_slice() {
+        [] => String::from(empty),
+        [(term, _)] => term.clone(),
+        _ => terms.into_iter()
+            .map(|(term, literals)| if literals > 1 { format!("({term})") } else { term })
+            .collect::<Vec<String>>()
+            .join(operator)
+    }
+}
+
+// the product of the literals of an implicant, with its amount of literals.
+fn product(implicant: &Implicant) -> (String, usize) {
+    let literals: Vec<String> = implicant.literals().into_iter()
+        .map(|(name, is_true)| {
+            if is_true { identifier(name) } else { format!("~{}", identifier(name)) }
+        })
+        .collect();
+
+    // an implicant where no variable matters is constant true.
+    if literals.is_empty() { return (String::from("1'b1"), 0) }
+    (literals.join(" & "), literals.len())
+}
+
+// for implicants built from the zeros of a function, the sum term that is false exactly where
+// the implicant holds.
+fn clause(implicant: &Implicant) -> (String, usize) {
+    let literals: Vec<String> = implicant.literals().into_iter()
+        .map(|(name, is_true)| {
+            if is_true { format!("~{}", identifier(name)) } else { identifier(name) }
+        })
+        .collect();
+
+    // a clause where no variable matters is constant false.
+    if literals.is_empty() { return (String::from("1'b0"), 0) }
+    (literals.join(" | "), literals.len())
+}
+
+// the module name is made a plain identifier, such as func_a for func-a, since it is usually
+// taken from the name of a file.
+fn module_name(name: &str) -> String {
+    let mut plain: String = name.chars()
+        .map(|symbol| if symbol.is_ascii_alphanumeric() || symbol == '_' { symbol } else { '_' })
+        .collect();
+    if ! plain.starts_with(|first: char| first.is_ascii_alphabetic() || first == '_')
+        || KEYWORDS.contains(&plain.as_str()) {
+        plain.insert(0, '_');
+    }
+    plain
+}
+
+// names that are not plain Verilog identifiers, or that are keywords, are written as escaped
+// identifiers: a backslash, the name, and a space that ends it.
+fn identifier(name: &str) -> String {
+    let starts_well = name.starts_with(|first: char| first.is_ascii_alphabetic() || first == '_');
+    let is_plain = starts_well
+        && name.chars().all(|symbol| symbol.is_ascii_alphanumeric() || "_$".contains(symbol));
+
+    if is_plain && ! KEYWORDS.contains(&name) { return name.to_string() }
+
+    // escaped identifiers may hold any printable character but spaces.
+    let escaped: String = name.chars().filter(|symbol| symbol.is_ascii_graphic()).collect();
+    if escaped.is_empty() { return String::from("_") }
+    format!("\\{escaped} ")
+}
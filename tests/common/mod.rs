@@ -0,0 +1,35 @@
+// helpers shared by the integration tests and the benchmarks. each of them uses only some of
+// these, so the others would be reported as unused.
+#![allow(dead_code)]
+
+use quine_mccluskey::{OutputValue, TruthTable};
+
+// a xorshift generator, so that the random tables are the same on every run.
+pub fn xorshift(seed: u64) -> impl FnMut() -> u64 {
+    let mut state = seed;
+    move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    }
+}
+
+// the values of the inputs of a minterm, the first variable being the most significant bit.
+pub fn input_row(minterm: usize, amount_of_variables: usize) -> Vec<bool> {
+    (0..amount_of_variables)
+        .map(|bit| minterm & (1 << (amount_of_variables - 1 - bit)) != 0)
+        .collect()
+}
+
+// whether the other table gives the same outputs, except where the table has a don't-care.
+pub fn same_values(table: &TruthTable, other: &TruthTable) -> bool {
+    table.variables_names() == other.variables_names()
+        && table.amount_of_outputs() == other.amount_of_outputs()
+        && (0..table.input_rows().len()).all(|row| {
+            (0..table.amount_of_outputs()).all(|output| {
+                let value = table.row_value(row, output);
+                value == OutputValue::DontCare || value == other.row_value(row, output)
+            })
+        })
+}
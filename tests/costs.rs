@@ -0,0 +1,46 @@
+// the costs the cost models give to the minimized expressions.
+
+use quine_mccluskey::{minimize_with, CostMetric, Options, TruthTable};
+
+fn table(variables: &[&str], minterms: &[usize]) -> TruthTable {
+    TruthTable::from_minterms(
+        variables.iter().map(|name| name.to_string()).collect(), String::from("F"), minterms, &[]
+    ).unwrap()
+}
+
+fn gate_inputs_options() -> Options {
+    let mut options = Options::default();
+    options.cost_model.metric = CostMetric::GateInputs;
+    options
+}
+
+#[test]
+fn gate_inputs_weight_a_single_literal_term() {
+    let mut options = gate_inputs_options();
+    options.cost_model.complemented_weight = 5;
+    let result = minimize_with(&table(&["A", "B"], &[0, 2]), &options).unwrap();
+    assert_eq!(result.expression(), "!B");
+    assert_eq!(result.cost(), 5);
+
+    let mut options = gate_inputs_options();
+    options.cost_model.variables_weights.insert(String::from("B"), 3);
+    let result = minimize_with(&table(&["A", "B"], &[1, 3]), &options).unwrap();
+    assert_eq!(result.expression(), "B");
+    assert_eq!(result.cost(), 3);
+}
+
+#[test]
+fn gate_inputs_count_the_joining_gate_for_bigger_terms() {
+    let result = minimize_with(&table(&["A", "B", "C"], &[0, 7]), &gate_inputs_options()).unwrap();
+    assert_eq!(result.expression(), "!A!B!C + ABC");
+    assert_eq!(result.cost(), 8);
+}
+
+#[test]
+fn huge_weights_saturate_instead_of_overflowing() {
+    let mut options = Options::default();
+    options.cost_model.complemented_weight = usize::MAX;
+    options.cost_model.variables_weights.insert(String::from("A"), usize::MAX);
+    let result = minimize_with(&table(&["A", "B"], &[0]), &options).unwrap();
+    assert_eq!(result.expression(), "!A!B");
+}
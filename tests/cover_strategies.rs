@@ -0,0 +1,63 @@
+// how the cover strategies choose among the primes left after the essential ones, on small
+// functions whose cyclic core leaves them a choice.
+
+use quine_mccluskey::cover_strategy::{strategy_by_name, Petrick};
+use quine_mccluskey::CoverStrategy;
+use quine_mccluskey::{minimize_with, Minimization, Options, TruthTable};
+
+// f(A,B,C) = Σm(0,1,2,5,6,7) has no essential primes: its six primes of two literals form a
+// cycle, covered by either !A!C + !BC + AB or !A!B + B!C + AC.
+fn cyclic_function() -> TruthTable {
+    TruthTable::from_minterms(
+        vec![String::from("A"), String::from("B"), String::from("C")], String::from("F"),
+        &[0, 1, 2, 5, 6, 7], &[]
+    ).unwrap()
+}
+
+fn minimize_weighting(cover: &str, variable: Option<&str>) -> Minimization {
+    let mut options = Options { cover: strategy_by_name(cover).unwrap(), ..Options::default() };
+    if let Some(variable) = variable {
+        options.cost_model.variables_weights.insert(String::from(variable), 10);
+    }
+    minimize_with(&cyclic_function(), &options).unwrap()
+}
+
+#[test]
+fn greedy_breaks_ties_by_cost() {
+    assert_eq!(minimize_weighting("greedy", None).expression(), "!A!C + !BC + AB");
+
+    // the first minterm is covered by !A!B and !A!C alike, and the weight of C makes the
+    // second one more expensive.
+    let result = minimize_weighting("greedy", Some("C"));
+    assert_eq!(result.expression(), "!A!B + B!C + AC");
+    assert_eq!(result.cost(), 24);
+}
+
+#[test]
+fn max_coverage_counts_only_the_terms_it_uses() {
+    let result = minimize_weighting("max-coverage", Some("C"));
+
+    assert_eq!(result.expression(), "!A!C + !BC + AB");
+    assert_eq!(result.amount_of_terms(), 3);
+    assert_eq!(result.amount_of_literals(), 6);
+    assert_eq!(result.cost(), 24);
+}
+
+// a cyclic core of 40 minterms, each covered by its own prime and the next one, whose product of
+// sums expands to hundreds of millions of products.
+#[test]
+fn petrick_falls_back_to_branch_and_bound_on_big_cores() {
+    let size = 40;
+    let incidence: Vec<Vec<bool>> = (0..size)
+        .map(|prime| (0..size).map(|minterm| minterm == prime || (minterm + 1) % size == prime)
+            .collect())
+        .collect();
+
+    let selection = Petrick.select(&incidence, &vec![1; size]).unwrap();
+
+    assert!(selection.explanation[0].contains("branch and bound"));
+    assert_eq!(selection.primes.len(), size / 2);
+    assert!((0..size).all(|minterm| {
+        selection.primes.iter().any(|prime| incidence[*prime][minterm])
+    }));
+}
@@ -0,0 +1,151 @@
+// the file formats and notations tables are read from and results are written in. results
+// written by the tool read back as the same function, and files too big to be kept as a table
+// are rejected with an error instead of crashing the tool.
+
+mod common;
+
+use std::fs;
+use std::path::PathBuf;
+use common::same_values;
+use quine_mccluskey::blif::write_blif;
+use quine_mccluskey::expression::{parse_expression, table_from_expressions};
+use quine_mccluskey::pla::write_pla;
+use quine_mccluskey::{minimize_with, Form, Minimization, Options, QmError, TruthTable};
+
+const FUNC_A: &str = "example_tables/func-a.csv";
+
+// a directory of the temporary directory, named after the test using it, which is removed with
+// the files written in it once the test ends, whether it passes or not.
+struct TemporaryDirectory(PathBuf);
+
+impl TemporaryDirectory {
+    fn new(test: &str) -> Self {
+        let path = std::env::temp_dir()
+            .join(format!("quine-mccluskey-{}-{test}", std::process::id()));
+        fs::create_dir_all(&path).unwrap();
+        TemporaryDirectory(path)
+    }
+
+    // writes the content into a file of the directory.
+    fn file(&self, name: &str, content: &str) -> String {
+        let path = self.0.join(name);
+        fs::write(&path, content).unwrap();
+        path.to_string_lossy().into_owned()
+    }
+}
+
+impl Drop for TemporaryDirectory {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+// tables of one output with don't-cares, and of several outputs, one of them constant.
+fn round_trip_tables() -> Vec<TruthTable> {
+    let several_outputs = table_from_expressions(
+        "F = AB + !C; G = A ^ C ^ D; H = B!C + AD; K = 0",
+        Some(vec![String::from("A"), String::from("B"), String::from("C"), String::from("D")])
+    ).unwrap();
+    vec![TruthTable::from_csv(FUNC_A).unwrap(), several_outputs]
+}
+
+// minimizes each table in both forms, and reads the written result back through read.
+fn assert_round_trip(
+    extension: &str,
+    write: &dyn Fn(&Minimization, &[String]) -> String,
+    read: &dyn Fn(&str) -> Result<TruthTable, QmError>
+) {
+    let directory = TemporaryDirectory::new(&format!("round-trip-{extension}"));
+    for (index, table) in round_trip_tables().iter().enumerate() {
+        for form in [Form::SumOfProducts, Form::ProductOfSums] {
+            let options = Options { form, ..Options::default() };
+            let minimization = minimize_with(table, &options).unwrap();
+            let content = write(&minimization, &table.variables_names());
+            let path = directory.file(&format!("{index}-{form:?}.{extension}"), &content);
+
+            let read_back = read(&path).unwrap();
+            assert!(same_values(table, &read_back), "{content} differs from table {index}");
+        }
+    }
+}
+
+fn assert_invalid_header<T>(result: Result<T, QmError>) {
+    match result {
+        Err(QmError::InvalidHeader(_)) => {},
+        Err(error) => panic!("expected an invalid header, but got: {error}"),
+        Ok(_) => panic!("expected an invalid header, but the table was read")
+    }
+}
+
+#[test]
+fn pla_with_too_many_inputs_is_rejected() {
+    let directory = TemporaryDirectory::new("pla-inputs");
+    for amount in [40, 64] {
+        let content = format!(".i {amount}\n.o 1\n{} 1\n.e\n", "-".repeat(amount));
+        let path = directory.file(&format!("inputs-{amount}.pla"), &content);
+        assert_invalid_header(TruthTable::from_pla(&path));
+    }
+}
+
+#[test]
+fn pla_with_too_many_outputs_is_rejected() {
+    let directory = TemporaryDirectory::new("pla-outputs");
+    let path = directory.file("outputs.pla", ".i 2\n.o 3000000000\n.e\n");
+    assert_invalid_header(TruthTable::from_pla(&path));
+}
+
+#[test]
+fn blif_with_too_many_inputs_is_rejected() {
+    let names: Vec<String> = (0..64).map(|input| format!("x{input}")).collect();
+    let content = format!(
+        ".model wide\n.inputs {}\n.outputs f\n.names {} f\n{} 1\n.end\n",
+        names.join(" "), names.join(" "), "-".repeat(64)
+    );
+    let directory = TemporaryDirectory::new("blif-inputs");
+    let path = directory.file("inputs-64.blif", &content);
+    assert_invalid_header(TruthTable::from_blif(&path));
+}
+
+#[test]
+fn minterms_of_too_many_variables_are_rejected() {
+    let variables: Vec<String> = (0..61).map(|variable| format!("x{variable}")).collect();
+    assert_invalid_header(TruthTable::from_minterms(variables, String::from("f"), &[0], &[]));
+}
+
+#[test]
+fn expression_of_too_many_variables_is_rejected() {
+    let product: Vec<String> = (0..64).map(|variable| format!("x{variable}")).collect();
+    assert_invalid_header(table_from_expressions(&product.join(" "), None));
+}
+
+#[test]
+fn pla_results_read_back_as_the_same_function() {
+    assert_round_trip("pla", &|minimization, variables| write_pla(minimization, variables),
+        &TruthTable::from_pla);
+}
+
+#[test]
+fn blif_results_read_back_as_the_same_function() {
+    assert_round_trip("blif", &|minimization, variables| write_blif(minimization, variables, "f"),
+        &TruthTable::from_blif);
+}
+
+#[test]
+fn csv_with_blank_column_names_is_rejected() {
+    let directory = TemporaryDirectory::new("blank-names");
+    for (index, header) in ["A,,F", "A, ,F"].iter().enumerate() {
+        let content = format!("{header}\n0,0,1\n0,1,0\n1,0,1\n1,1,0\n");
+        let path = directory.file(&format!("blank-name-{index}.csv"), &content);
+        assert_invalid_header(TruthTable::from_csv(&path));
+    }
+}
+
+#[test]
+fn expression_over_a_blank_variable_name_reports_the_unknown_variable() {
+    let variables = vec![String::from("A"), String::new()];
+    match parse_expression("AX", &variables) {
+        Err(QmError::Notation { position: 2, .. }) => {},
+        Err(error) => panic!("expected an unknown variable at character 2, but got: {error}"),
+        Ok(_) => panic!("expected an unknown variable, but the expression was read")
+    }
+}
@@ -0,0 +1,99 @@
+// the results expected for the example tables, which guard the output of the tool against
+// unintended changes.
+
+mod common;
+
+use common::same_values;
+use quine_mccluskey::expression::table_from_expressions;
+use quine_mccluskey::verilog::write_verilog;
+use quine_mccluskey::{minimize, minimize_with, Engine, Form, Options, TruthTable};
+
+const FUNC_A: &str = "example_tables/func-a.csv";
+const FUNC_B: &str = "example_tables/func-b.csv";
+
+fn minimize_in(path: &str, form: Form) -> String {
+    let table = TruthTable::from_csv(path).unwrap();
+    let options = Options { form, ..Options::default() };
+    minimize_with(&table, &options).unwrap().expression()
+}
+
+#[test]
+fn func_a_sum_of_products() {
+    let table = TruthTable::from_csv(FUNC_A).unwrap();
+    let result = minimize(&table).unwrap();
+
+    assert_eq!(result.expression(), "!B!D + A!B!C + !AC + !ABD + BC");
+    assert_eq!(result.amount_of_terms(), 5);
+    assert_eq!(result.amount_of_literals(), 12);
+}
+
+#[test]
+fn func_a_product_of_sums() {
+    assert_eq!(
+        minimize_in(FUNC_A, Form::ProductOfSums),
+        "(A + B + C + !D)(!B + C + D)(!A + !B + C)(!A + B + !C + !D)"
+    );
+}
+
+#[test]
+fn func_a_best_form_is_the_sum_of_products() {
+    assert_eq!(minimize_in(FUNC_A, Form::Best), "!B!D + A!B!C + !AC + !ABD + BC");
+}
+
+#[test]
+fn func_a_reads_the_same_from_pla() {
+    let table = TruthTable::from_pla("example_tables/func-a.pla").unwrap();
+    assert_eq!(minimize(&table).unwrap().expression(), "!B!D + A!B!C + !AC + !ABD + BC");
+}
+
+#[test]
+fn func_a_espresso_finds_as_few_literals() {
+    let table = TruthTable::from_csv(FUNC_A).unwrap();
+    let options = Options { engine: Engine::Espresso, ..Options::default() };
+    assert_eq!(minimize_with(&table, &options).unwrap().amount_of_literals(), 12);
+}
+
+#[test]
+fn func_a_verilog_module() {
+    let table = TruthTable::from_csv(FUNC_A).unwrap();
+    let result = minimize(&table).unwrap();
+
+    assert_eq!(
+        write_verilog(&result, &table.variables_names(), "func-a"),
+        "module func_a (\n    input wire A,\n    input wire B,\n    input wire C,\n    \
+        input wire D,\n    output wire S\n);\n    \
+        assign S = (~B & ~D) | (A & ~B & ~C) | (~A & C) | (~A & B & D) | (B & C);\nendmodule\n"
+    );
+}
+
+#[test]
+fn func_b_sum_of_products() {
+    let table = TruthTable::from_csv(FUNC_B).unwrap();
+    let result = minimize(&table).unwrap();
+
+    assert_eq!(result.expression(), "!A!C!D + !B!D + !B!C + A!CD + !ACD");
+    assert_eq!(result.amount_of_terms(), 5);
+    assert_eq!(result.amount_of_literals(), 13);
+}
+
+#[test]
+fn func_b_product_of_sums() {
+    assert_eq!(
+        minimize_in(FUNC_B, Form::ProductOfSums),
+        "(A + !B + C + !D)(!A + !B + D)(!B + !C + D)(!A + !C + !D)"
+    );
+}
+
+#[test]
+fn results_read_back_as_the_same_function() {
+    for path in [FUNC_A, FUNC_B] {
+        let table = TruthTable::from_csv(path).unwrap();
+        for form in [Form::SumOfProducts, Form::ProductOfSums] {
+            let options = Options { form, ..Options::default() };
+            let expression = minimize_with(&table, &options).unwrap().expression();
+            let read_back = table_from_expressions(&expression, Some(table.variables_names()))
+                .unwrap();
+            assert!(same_values(&table, &read_back), "{expression} differs from {path}");
+        }
+    }
+}
@@ -0,0 +1,75 @@
+// with the parallel feature, the groups of implicants are combined on several threads. the primes
+// found must be the same, and in the same order, as when the groups are combined one after the
+// other, which is what a pool of a single thread does.
+#![cfg(feature = "parallel")]
+
+mod common;
+
+use common::{input_row, xorshift};
+use quine_mccluskey::implicant::VariablesNames;
+use quine_mccluskey::{GroupStructure, Implicant};
+use rayon::ThreadPoolBuilder;
+
+// the minterms of a random table, each implying a random set of its outputs, from a xorshift
+// generator so that every run combines the same tables.
+fn random_implicants(amount_of_variables: usize, amount_of_outputs: usize, seed: u64)
+    -> Vec<Implicant>
+{
+    let mut next = xorshift(seed);
+    let variables: VariablesNames = (0..amount_of_variables)
+        .map(|variable| format!("X{variable}"))
+        .collect();
+    let all_outputs = (1u64 << amount_of_outputs) - 1;
+
+    (0..1usize << amount_of_variables)
+        .filter_map(|minterm| {
+            let outputs = next() & all_outputs;
+            if outputs == 0 { return None }
+            let row = input_row(minterm, amount_of_variables);
+            Some(Implicant::from_input(&row, &variables, outputs))
+        })
+        .collect()
+}
+
+// the primes found on a pool of the given amount of threads, as their cubes and outputs.
+fn primes_on_threads(
+    threads: usize,
+    amount_of_variables: usize,
+    amount_of_outputs: usize,
+    implicants: &[Implicant]
+) -> Vec<String> {
+    let pool = ThreadPoolBuilder::new().num_threads(threads).build().unwrap();
+    pool.install(|| {
+        let mut groups = GroupStructure::new(amount_of_variables, amount_of_outputs);
+        implicants.iter().for_each(|implicant| groups.add_implicant(implicant.clone()));
+        while groups.combination_step().unwrap() {}
+
+        groups.extract_primes().unwrap().iter()
+            .map(|prime| format!(
+                "{} {}",
+                prime.get_binary_representation(),
+                prime.get_outputs_representation(amount_of_outputs)
+            ))
+            .collect()
+    })
+}
+
+#[test]
+fn several_threads_find_the_same_primes_in_the_same_order() {
+    for seed in 1..=12u64 {
+        let amount_of_variables = 6 + (seed % 5) as usize;
+        let amount_of_outputs = 1 + (seed % 3) as usize;
+        let implicants = random_implicants(
+            amount_of_variables, amount_of_outputs, seed.wrapping_mul(0x9e3779b97f4a7c15)
+        );
+
+        let sequential = primes_on_threads(1, amount_of_variables, amount_of_outputs, &implicants);
+        for threads in [2, 4, 8] {
+            assert_eq!(
+                primes_on_threads(threads, amount_of_variables, amount_of_outputs, &implicants),
+                sequential,
+                "{threads} threads differ on the table of seed {seed}"
+            );
+        }
+    }
+}
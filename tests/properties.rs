@@ -0,0 +1,243 @@
+// properties checked on random tables of up to 6 variables. the results are evaluated by hand
+// here instead of through the verifier of the crate, and the exact cover strategies are compared
+// against a brute-force search that shares no code with the minimizer.
+
+use proptest::prelude::*;
+use quine_mccluskey::cover_strategy::strategy_by_name;
+use quine_mccluskey::table_parser::{all_input_rows, default_output_names, default_variables_names};
+use quine_mccluskey::{minimize_with, Engine, Form, Implicant, Minimization, Options};
+use quine_mccluskey::{OutputValue, TruthTable};
+
+const MAX_VARIABLES: usize = 6;
+const MAX_OUTPUTS: usize = 3;
+
+fn output_value() -> impl Strategy<Value = OutputValue> {
+    prop_oneof![
+        3 => Just(OutputValue::True),
+        3 => Just(OutputValue::False),
+        1 => Just(OutputValue::DontCare)
+    ]
+}
+
+// the values of every row of a table, for each of its outputs.
+fn table_values(max_outputs: usize) -> impl Strategy<Value = Vec<Vec<OutputValue>>> {
+    (1..=MAX_VARIABLES, 1..=max_outputs).prop_flat_map(|(amount_of_variables, amount_of_outputs)| {
+        prop::collection::vec(
+            prop::collection::vec(output_value(), amount_of_outputs),
+            1 << amount_of_variables
+        )
+    })
+}
+
+fn build_table(values: Vec<Vec<OutputValue>>) -> TruthTable {
+    let amount_of_variables = values.len().trailing_zeros() as usize;
+    let amount_of_outputs = values[0].len();
+    TruthTable::new(
+        default_variables_names(amount_of_variables),
+        default_output_names(amount_of_outputs),
+        all_input_rows(amount_of_variables).unwrap(),
+        values
+    ).expect("random tables are well formed")
+}
+
+fn options(form: Form, engine: Engine, cover: &str) -> Options {
+    Options {
+        form,
+        engine,
+        cover: strategy_by_name(cover).expect("the strategy exists"),
+        ..Options::default()
+    }
+}
+
+// whether the term holds for a row, read from its literals alone.
+fn term_holds(term: &Implicant, row: &[bool], variables: &[String]) -> bool {
+    term.literals().iter().all(|(name, is_true)| {
+        let variable = variables.iter().position(|variable| variable == name).unwrap();
+        row[variable] == *is_true
+    })
+}
+
+// the value the minimization gives to an output at a row. a product of sums is false where some
+// of its terms, which describe the zeros, holds.
+fn evaluate(minimization: &Minimization, output: usize, row: &[bool], variables: &[String])
+    -> bool
+{
+    let some_term_holds = minimization.output_cover(output).iter()
+        .any(|term| term_holds(term, row, variables));
+    match minimization.form() {
+        Form::ProductOfSums => ! some_term_holds,
+        _ => some_term_holds
+    }
+}
+
+fn assert_matches_table(table: &TruthTable, minimization: &Minimization) {
+    let variables = table.variables_names();
+    for (index, row) in table.input_rows().iter().enumerate() {
+        for output in 0..table.amount_of_outputs() {
+            let expected = match table.row_value(index, output) {
+                OutputValue::True => true,
+                OutputValue::False => false,
+                OutputValue::DontCare => continue
+            };
+            assert_eq!(
+                evaluate(minimization, output, row, &variables), expected,
+                "{} is wrong at row {index} of output {output}", minimization.expression()
+            );
+        }
+    }
+}
+
+// the minterms inside a cube given by its value and mask words.
+fn cube_minterms(value: u64, mask: u64, amount_of_variables: usize) -> Vec<usize> {
+    (0..1usize << amount_of_variables)
+        .filter(|minterm| (*minterm as u64 ^ value) & ! mask == 0)
+        .collect()
+}
+
+// a cube is an implicant of some outputs when it takes in none of their zeros. the zeros of a
+// product of sums are the ones of the table.
+fn is_implicant(table: &TruthTable, form: Form, value: u64, mask: u64, outputs: u64) -> bool {
+    let zero = match form {
+        Form::ProductOfSums => OutputValue::True,
+        _ => OutputValue::False
+    };
+    cube_minterms(value, mask, table.amount_of_variables()).iter().all(|minterm| {
+        (0..table.amount_of_outputs())
+            .filter(|output| outputs & (1 << output) != 0)
+            .all(|output| table.row_value(*minterm, output) != zero)
+    })
+}
+
+fn assert_primes_are_prime(table: &TruthTable, minimization: &Minimization) {
+    for prime in minimization.primes() {
+        let (value, mask, outputs) = (prime.value(), prime.mask(), prime.outputs());
+        assert!(
+            is_implicant(table, minimization.form(), value, mask, outputs),
+            "{} takes in a zero of its outputs", prime.get_binary_representation()
+        );
+
+        for variable in 0..table.amount_of_variables() {
+            let bit = 1u64 << variable;
+            if mask & bit != 0 { continue }
+            assert!(
+                ! is_implicant(table, minimization.form(), value & ! bit, mask | bit, outputs),
+                "{} is not prime, it still grows without its variable {}",
+                prime.get_binary_representation(), table.amount_of_variables() - 1 - variable
+            );
+        }
+    }
+}
+
+// the least amount of literals of a sum of products for the first output of the table, found by
+// trying every cover made of primes. some cover of the least amount of literals is always made of
+// primes, since every term can be grown into a prime with fewer literals.
+fn oracle_literals(table: &TruthTable) -> usize {
+    let amount_of_variables = table.amount_of_variables();
+    let all_variables = (1u64 << amount_of_variables) - 1;
+
+    // every cube that takes in no zero, found among all 3^n cubes.
+    let mut implicants: Vec<(u64, u64)> = Vec::new();
+    for mask in 0..=all_variables {
+        for value in 0..=all_variables {
+            if value & mask != 0 { continue }
+            if is_implicant(table, Form::SumOfProducts, value, mask, 1) {
+                implicants.push((value, mask));
+            }
+        }
+    }
+    let contains = |(value, mask): (u64, u64), (other_value, other_mask): (u64, u64)| {
+        other_mask & mask == mask && (value ^ other_value) & ! other_mask == 0
+    };
+    let primes: Vec<(u64, u64)> = implicants.iter()
+        .filter(|cube| {
+            ! implicants.iter().any(|other| other != *cube && contains(**cube, *other))
+        })
+        .copied()
+        .collect();
+
+    let ones: Vec<usize> = (0..1usize << amount_of_variables)
+        .filter(|minterm| table.row_value(*minterm, 0) == OutputValue::True)
+        .collect();
+    let literals = |(_, mask): (u64, u64)| amount_of_variables - mask.count_ones() as usize;
+
+    let mut best = usize::MAX;
+    search(&ones, &primes, &literals, &mut vec![false; ones.len()], 0, &mut best);
+    best
+}
+
+// branches on the primes that cover the first uncovered one, keeping the cheapest cover found.
+fn search(
+    ones: &[usize],
+    primes: &[(u64, u64)],
+    literals: &dyn Fn((u64, u64)) -> usize,
+    covered: &mut Vec<bool>,
+    cost: usize,
+    best: &mut usize
+) {
+    if cost >= *best { return }
+    let Some(uncovered) = covered.iter().position(|covered| ! covered) else {
+        *best = cost;
+        return
+    };
+
+    let minterm = ones[uncovered] as u64;
+    for prime in primes.iter().filter(|(value, mask)| (minterm ^ value) & ! mask == 0) {
+        let before = covered.clone();
+        for (index, one) in ones.iter().enumerate() {
+            if (*one as u64 ^ prime.0) & ! prime.1 == 0 { covered[index] = true }
+        }
+        search(ones, primes, literals, covered, cost + literals(*prime), best);
+        *covered = before;
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn quine_mccluskey_matches_the_table(values in table_values(MAX_OUTPUTS)) {
+        let table = build_table(values);
+        for form in [Form::SumOfProducts, Form::ProductOfSums] {
+            let options = options(form, Engine::QuineMcCluskey, "greedy");
+            let minimization = minimize_with(&table, &options).unwrap();
+            assert_matches_table(&table, &minimization);
+        }
+    }
+
+    #[test]
+    fn espresso_matches_the_table(values in table_values(MAX_OUTPUTS)) {
+        let table = build_table(values);
+        for form in [Form::SumOfProducts, Form::ProductOfSums] {
+            let options = options(form, Engine::Espresso, "greedy");
+            let minimization = minimize_with(&table, &options).unwrap();
+            assert_matches_table(&table, &minimization);
+        }
+    }
+
+    #[test]
+    fn every_prime_is_prime(values in table_values(MAX_OUTPUTS)) {
+        let table = build_table(values);
+        for engine in [Engine::QuineMcCluskey, Engine::Espresso] {
+            for form in [Form::SumOfProducts, Form::ProductOfSums] {
+                let minimization = minimize_with(&table, &options(form, engine, "greedy")).unwrap();
+                assert_primes_are_prime(&table, &minimization);
+            }
+        }
+    }
+
+    #[test]
+    fn exact_strategies_find_the_least_literals(values in table_values(1)) {
+        let table = build_table(values);
+        let least_literals = oracle_literals(&table);
+        for cover in ["branch-and-bound", "petrick"] {
+            let minimization = minimize_with(
+                &table, &options(Form::SumOfProducts, Engine::QuineMcCluskey, cover)
+            ).unwrap();
+            assert_matches_table(&table, &minimization);
+            prop_assert_eq!(
+                minimization.amount_of_literals(), least_literals,
+                "{} found {}", cover, minimization.expression()
+            );
+        }
+    }
+}
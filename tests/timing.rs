@@ -0,0 +1,57 @@
+// guards the running time of the minimization against regressions that make large tables
+// impractical. the bounds are generous, so that they only fail when the work grows by orders of
+// magnitude. wall-clock time still depends on the machine, so these tests are left out of the
+// usual runs, and are meant to be run in release mode with
+//     cargo test --release --test timing -- --ignored
+
+mod common;
+
+use std::time::{Duration, Instant};
+use common::xorshift;
+use quine_mccluskey::table_parser::{all_input_rows, default_output_names, default_variables_names};
+use quine_mccluskey::verifier::verify_minimization;
+use quine_mccluskey::{minimize, minimize_with, Options, OutputValue, TruthTable};
+
+// a table with half of its rows true on average, from a xorshift generator, so that every run
+// minimizes the same function.
+fn random_table(amount_of_variables: usize, seed: u64) -> TruthTable {
+    let mut next = xorshift(seed);
+    let outputs = (0..1usize << amount_of_variables)
+        .map(|_| vec![if next().is_multiple_of(2) { OutputValue::True } else { OutputValue::False }])
+        .collect();
+
+    TruthTable::new(
+        default_variables_names(amount_of_variables),
+        default_output_names(1),
+        all_input_rows(amount_of_variables).unwrap(),
+        outputs
+    ).unwrap()
+}
+
+#[test]
+#[ignore = "measures wall-clock time, run it with --ignored in release mode"]
+fn random_table_with_11_variables_is_minimized_in_seconds() {
+    let table = random_table(11, 0x9e3779b97f4a7c15);
+
+    let start = Instant::now();
+    let result = minimize(&table).unwrap();
+    let elapsed = start.elapsed();
+
+    assert!(verify_minimization(&table, &result).is_empty());
+    assert!(elapsed < Duration::from_secs(20), "the minimization took {elapsed:?}");
+}
+
+#[test]
+#[ignore = "measures wall-clock time, run it with --ignored in release mode"]
+fn minimum_covers_of_a_random_table_with_8_variables_are_listed_in_seconds() {
+    let table = random_table(8, 0x2545f4914f6cdd1d);
+    let options = Options { all_solutions: true, max_solutions: Some(10), ..Options::default() };
+
+    let start = Instant::now();
+    let result = minimize_with(&table, &options).unwrap();
+    let elapsed = start.elapsed();
+
+    assert!(! result.minimum_solutions().is_empty());
+    assert!(result.minimum_solutions().len() <= 10);
+    assert!(elapsed < Duration::from_secs(20), "listing the covers took {elapsed:?}");
+}